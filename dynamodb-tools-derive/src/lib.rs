@@ -0,0 +1,495 @@
+//! `#[derive(DynamoTable)]` for [dynamodb-tools](https://docs.rs/dynamodb-tools).
+//!
+//! Use it through the `derive` feature of `dynamodb-tools` rather than directly.
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::quote;
+use syn::ext::IdentExt;
+use syn::meta::ParseNestedMeta;
+use syn::{
+    Data, DeriveInput, Error, Fields, GenericArgument, LitStr, PathArguments, Result, Type,
+    parse_macro_input,
+};
+
+/// Implements `dynamodb_tools::DynamoTable` for a struct with named fields.
+///
+/// Container attribute:
+/// - `#[dynamo(table = "users")]`: the base table name. Defaults to the struct
+///   name in snake case.
+///
+/// Field attributes (a field may carry several `#[dynamo(...)]` attributes):
+/// - `#[dynamo(pk)]` / `#[dynamo(sk)]`: the partition / sort key of the table.
+/// - `#[dynamo(gsi = "gsi1", pk)]` / `#[dynamo(gsi = "gsi1", sk)]`: a key of a GSI.
+/// - `#[dynamo(lsi = "lsi1")]`: the sort key of an LSI.
+/// - `#[dynamo(attr_type = "S")]`: the attribute type (`S`, `N` or `B`), for
+///   field types it cannot be inferred from.
+///
+/// Attribute names follow serde: `#[serde(rename = "...")]` (or its `serialize`
+/// name) on a field, otherwise the container's `#[serde(rename_all = "...")]`.
+/// Indexes project all attributes.
+#[proc_macro_derive(DynamoTable, attributes(dynamo))]
+pub fn derive_dynamo_table(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+// A key attribute: its name and DynamoDB type (`S`, `N` or `B`)
+#[derive(Debug, Clone, PartialEq)]
+struct KeyAttr {
+    name: String,
+    attr_type: String,
+}
+
+#[derive(Default)]
+struct Gsi {
+    name: String,
+    pk: Option<KeyAttr>,
+    sk: Option<KeyAttr>,
+}
+
+#[derive(Default)]
+struct Schema {
+    pk: Option<KeyAttr>,
+    sk: Option<KeyAttr>,
+    gsis: Vec<Gsi>,
+    lsis: Vec<(String, KeyAttr)>,
+}
+
+// What one `#[dynamo(...)]` field attribute declares
+#[derive(Default)]
+struct FieldAttr {
+    pk: bool,
+    sk: bool,
+    gsi: Option<String>,
+    lsi: Option<String>,
+    attr_type: Option<LitStr>,
+}
+
+fn expand(input: DeriveInput) -> Result<TokenStream2> {
+    let Data::Struct(data) = &input.data else {
+        return Err(Error::new_spanned(
+            &input.ident,
+            "DynamoTable can only be derived for structs",
+        ));
+    };
+    let Fields::Named(fields) = &data.fields else {
+        return Err(Error::new_spanned(
+            &input.ident,
+            "DynamoTable requires a struct with named fields",
+        ));
+    };
+
+    let mut table_name = snake_case(&input.ident.to_string());
+    for attr in input.attrs.iter().filter(|a| a.path().is_ident("dynamo")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("table") {
+                table_name = meta.value()?.parse::<LitStr>()?.value();
+                Ok(())
+            } else {
+                Err(meta.error("expected `table = \"...\"`"))
+            }
+        })?;
+    }
+    let mut rename_all = None;
+    for attr in input.attrs.iter().filter(|a| a.path().is_ident("serde")) {
+        // Only `rename_all` matters here. Serde itself reports malformed attributes
+        let _ = attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("rename_all") {
+                if let Some(rule) = serialize_name(&meta)? {
+                    rename_all = Some(rule);
+                }
+                Ok(())
+            } else {
+                skip_meta(&meta)
+            }
+        });
+    }
+    if let Some(ref rule) = rename_all
+        && rename_field("field", &rule.value()).is_none()
+    {
+        return Err(Error::new_spanned(
+            rule,
+            format!("unknown serde rename rule `{}`", rule.value()),
+        ));
+    }
+
+    let mut schema = Schema::default();
+    for field in &fields.named {
+        let ident = field.ident.as_ref().expect("named field");
+        let field_name = ident.unraw().to_string();
+        let mut name = match rename_all {
+            Some(ref rule) => rename_field(&field_name, &rule.value()).expect("checked above"),
+            None => field_name,
+        };
+        let mut decls = Vec::new();
+        for attr in &field.attrs {
+            if attr.path().is_ident("serde") {
+                // Only `rename` matters here. Serde itself reports malformed attributes
+                let _ = attr.parse_nested_meta(|meta| {
+                    if meta.path.is_ident("rename") {
+                        if let Some(rename) = serialize_name(&meta)? {
+                            name = rename.value();
+                        }
+                        Ok(())
+                    } else {
+                        skip_meta(&meta)
+                    }
+                });
+            } else if attr.path().is_ident("dynamo") {
+                decls.push((attr, parse_field_attr(attr)?));
+            }
+        }
+        if decls.is_empty() {
+            continue;
+        }
+
+        let override_type = decls.iter().find_map(|(_, d)| d.attr_type.clone());
+        let attr_type = match override_type {
+            Some(lit) => match lit.value().as_str() {
+                t @ ("S" | "N" | "B") => t.to_string(),
+                _ => return Err(Error::new_spanned(lit, "expected \"S\", \"N\" or \"B\"")),
+            },
+            None => infer_attr_type(&field.ty)
+                .ok_or_else(|| {
+                    Error::new_spanned(
+                        &field.ty,
+                        format!(
+                            "cannot infer the DynamoDB type of `{}`; add `#[dynamo(attr_type = \"S\")]` (or \"N\", \"B\")",
+                            ident
+                        ),
+                    )
+                })?
+                .to_string(),
+        };
+        let key = KeyAttr { name, attr_type };
+
+        for (attr, decl) in decls {
+            schema.add(attr, decl, &key)?;
+        }
+    }
+
+    let pk = schema.pk.as_ref().ok_or_else(|| {
+        Error::new(
+            Span::call_site(),
+            "DynamoTable requires a field marked `#[dynamo(pk)]`",
+        )
+    })?;
+    let mut key_names = vec![pk.name.clone()];
+    key_names.extend(schema.sk.iter().map(|sk| sk.name.clone()));
+
+    // Index keys that are not table keys are declared as extra attributes
+    let mut attrs: Vec<&KeyAttr> = Vec::new();
+    let index_keys = schema
+        .gsis
+        .iter()
+        .flat_map(|gsi| gsi.pk.iter().chain(gsi.sk.iter()))
+        .chain(schema.lsis.iter().map(|(_, sk)| sk));
+    for key in index_keys {
+        if !key_names.contains(&key.name) && !attrs.iter().any(|a| a.name == key.name) {
+            attrs.push(key);
+        }
+    }
+
+    let mut gsis = Vec::new();
+    for gsi in &schema.gsis {
+        let gsi_pk = gsi.pk.as_ref().ok_or_else(|| {
+            Error::new(
+                Span::call_site(),
+                format!("GSI `{}` has no field marked `pk`", gsi.name),
+            )
+        })?;
+        let name = &gsi.name;
+        let gsi_pk = table_attr(gsi_pk);
+        let gsi_sk = optional_table_attr(gsi.sk.as_ref());
+        gsis.push(quote! {{
+            let mut gsi = ::dynamodb_tools::TableGsi::new(#name, #gsi_pk, #gsi_sk);
+            gsi.projection = ::std::option::Option::Some(::dynamodb_tools::IndexProjection::All);
+            gsi
+        }});
+    }
+    let lsis = schema.lsis.iter().map(|(name, sk)| {
+        let lsi_pk = table_attr(pk);
+        let lsi_sk = table_attr(sk);
+        quote! {{
+            let mut lsi = ::dynamodb_tools::TableLsi::new(#name, #lsi_pk, #lsi_sk);
+            lsi.projection = ::std::option::Option::Some(::dynamodb_tools::IndexProjection::All);
+            lsi
+        }}
+    });
+    let table_pk = table_attr(pk);
+    let table_sk = optional_table_attr(schema.sk.as_ref());
+    let attrs = attrs.into_iter().map(table_attr);
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::dynamodb_tools::DynamoTable for #ident #ty_generics #where_clause {
+            fn table_info() -> ::dynamodb_tools::TableInfo {
+                let mut info = ::dynamodb_tools::TableInfo::new(#table_name, #table_pk, #table_sk);
+                info.attrs = ::std::vec![#(#attrs),*];
+                info.gsis = ::std::vec![#(#gsis),*];
+                info.lsis = ::std::vec![#(#lsis),*];
+                info
+            }
+        }
+    })
+}
+
+// The serialized name of `rename = "..."` or `rename(serialize = "...")` (and the same
+// for `rename_all`); items are written with serde, so deserialize names do not matter
+fn serialize_name(meta: &ParseNestedMeta) -> Result<Option<LitStr>> {
+    if meta.input.peek(syn::Token![=]) {
+        return Ok(Some(meta.value()?.parse()?));
+    }
+    let mut name = None;
+    meta.parse_nested_meta(|nested| {
+        let value = nested.value()?.parse::<LitStr>()?;
+        if nested.path.is_ident("serialize") {
+            name = Some(value);
+        }
+        Ok(())
+    })?;
+    Ok(name)
+}
+
+// Consumes a serde attribute this macro does not care about
+fn skip_meta(meta: &ParseNestedMeta) -> Result<()> {
+    if meta.input.peek(syn::Token![=]) {
+        meta.value()?.parse::<syn::Expr>()?;
+    } else if meta.input.peek(syn::token::Paren) {
+        let _ = meta.parse_nested_meta(|nested| {
+            if nested.input.peek(syn::Token![=]) {
+                nested.value()?.parse::<syn::Expr>()?;
+            }
+            Ok(())
+        });
+    }
+    Ok(())
+}
+
+// Applies a serde `rename_all` rule to a snake_case field name the way serde does;
+// `None` for an unknown rule
+fn rename_field(field: &str, rule: &str) -> Option<String> {
+    let pascal = || {
+        let mut out = String::new();
+        let mut capitalize = true;
+        for c in field.chars() {
+            if c == '_' {
+                capitalize = true;
+            } else if capitalize {
+                out.push(c.to_ascii_uppercase());
+                capitalize = false;
+            } else {
+                out.push(c);
+            }
+        }
+        out
+    };
+    Some(match rule {
+        "lowercase" | "snake_case" => field.to_string(),
+        "UPPERCASE" | "SCREAMING_SNAKE_CASE" => field.to_ascii_uppercase(),
+        "PascalCase" => pascal(),
+        "camelCase" => {
+            let pascal = pascal();
+            let mut chars = pascal.chars();
+            chars
+                .next()
+                .map(|c| c.to_ascii_lowercase().to_string() + chars.as_str())
+                .unwrap_or_default()
+        }
+        "kebab-case" => field.replace('_', "-"),
+        "SCREAMING-KEBAB-CASE" => field.to_ascii_uppercase().replace('_', "-"),
+        _ => return None,
+    })
+}
+
+fn parse_field_attr(attr: &syn::Attribute) -> Result<FieldAttr> {
+    let mut decl = FieldAttr::default();
+    attr.parse_nested_meta(|meta| {
+        if meta.path.is_ident("pk") {
+            decl.pk = true;
+        } else if meta.path.is_ident("sk") {
+            decl.sk = true;
+        } else if meta.path.is_ident("gsi") {
+            decl.gsi = Some(meta.value()?.parse::<LitStr>()?.value());
+        } else if meta.path.is_ident("lsi") {
+            decl.lsi = Some(meta.value()?.parse::<LitStr>()?.value());
+        } else if meta.path.is_ident("attr_type") {
+            decl.attr_type = Some(meta.value()?.parse()?);
+        } else {
+            return Err(meta.error("expected `pk`, `sk`, `gsi`, `lsi` or `attr_type`"));
+        }
+        Ok(())
+    })?;
+    Ok(decl)
+}
+
+impl Schema {
+    fn add(&mut self, attr: &syn::Attribute, decl: FieldAttr, key: &KeyAttr) -> Result<()> {
+        let error = |msg: &str| Err(Error::new_spanned(attr, msg));
+        if decl.pk && decl.sk {
+            return error("a key cannot be both `pk` and `sk`");
+        }
+        match (decl.gsi, decl.lsi) {
+            (Some(_), Some(_)) => error("`gsi` and `lsi` cannot be combined"),
+            (Some(name), None) => {
+                let gsi = match self.gsis.iter_mut().position(|g| g.name == name) {
+                    Some(i) => &mut self.gsis[i],
+                    None => {
+                        self.gsis.push(Gsi {
+                            name,
+                            ..Default::default()
+                        });
+                        self.gsis.last_mut().expect("just pushed")
+                    }
+                };
+                let slot = match (decl.pk, decl.sk) {
+                    (true, _) => &mut gsi.pk,
+                    (_, true) => &mut gsi.sk,
+                    _ => return error("`gsi` requires `pk` or `sk`"),
+                };
+                if slot.replace(key.clone()).is_some() {
+                    return error("duplicate GSI key");
+                }
+                Ok(())
+            }
+            (None, Some(name)) => {
+                if decl.pk || decl.sk {
+                    return error("`lsi` marks the sort key of the LSI; drop `pk`/`sk`");
+                }
+                if self.lsis.iter().any(|(n, _)| *n == name) {
+                    return error("duplicate LSI");
+                }
+                self.lsis.push((name, key.clone()));
+                Ok(())
+            }
+            (None, None) => {
+                let slot = match (decl.pk, decl.sk) {
+                    (true, _) => &mut self.pk,
+                    (_, true) => &mut self.sk,
+                    _ if decl.attr_type.is_some() => return Ok(()),
+                    _ => return error("expected `pk`, `sk`, `gsi` or `lsi`"),
+                };
+                if slot.replace(key.clone()).is_some() {
+                    return error("duplicate table key");
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+fn table_attr(key: &KeyAttr) -> TokenStream2 {
+    let name = &key.name;
+    let attr_type = syn::Ident::new(&key.attr_type, Span::call_site());
+    quote! {
+        ::dynamodb_tools::TableAttr {
+            name: ::std::string::String::from(#name),
+            attr_type: ::dynamodb_tools::AttrType::#attr_type,
+        }
+    }
+}
+
+fn optional_table_attr(key: Option<&KeyAttr>) -> TokenStream2 {
+    match key.map(table_attr) {
+        Some(attr) => quote!(::std::option::Option::Some(#attr)),
+        None => quote!(::std::option::Option::None),
+    }
+}
+
+// Infers the DynamoDB type of a field from how serde serializes its Rust type
+fn infer_attr_type(ty: &Type) -> Option<&'static str> {
+    match ty {
+        Type::Reference(r) => infer_attr_type(&r.elem),
+        Type::Paren(p) => infer_attr_type(&p.elem),
+        Type::Group(g) => infer_attr_type(&g.elem),
+        Type::Path(p) => {
+            let segment = p.path.segments.last()?;
+            match segment.ident.to_string().as_str() {
+                "String" | "str" | "char" | "Uuid" | "Ulid" | "DateTime" | "NaiveDate"
+                | "NaiveDateTime" => Some("S"),
+                "i8" | "i16" | "i32" | "i64" | "i128" | "isize" | "u8" | "u16" | "u32" | "u64"
+                | "u128" | "usize" | "f32" | "f64" => Some("N"),
+                "Bytes" | "ByteBuf" => Some("B"),
+                // Wrappers serialized as their content
+                "Option" | "Box" | "Rc" | "Arc" | "Cow" => {
+                    let PathArguments::AngleBracketed(args) = &segment.arguments else {
+                        return None;
+                    };
+                    args.args.iter().find_map(|arg| match arg {
+                        GenericArgument::Type(ty) => infer_attr_type(ty),
+                        _ => None,
+                    })
+                }
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+fn snake_case(name: &str) -> String {
+    let mut out = String::new();
+    for (i, c) in name.chars().enumerate() {
+        if c.is_uppercase() {
+            if i > 0 {
+                out.push('_');
+            }
+            out.extend(c.to_lowercase());
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::parse_quote;
+
+    #[test]
+    fn attr_type_should_be_inferred_from_field_types() {
+        let cases: Vec<(Type, Option<&str>)> = vec![
+            (parse_quote!(String), Some("S")),
+            (parse_quote!(&'a str), Some("S")),
+            (parse_quote!(uuid::Uuid), Some("S")),
+            (parse_quote!(Option<u64>), Some("N")),
+            (parse_quote!(Cow<'a, str>), Some("S")),
+            (parse_quote!(f64), Some("N")),
+            (parse_quote!(serde_bytes::ByteBuf), Some("B")),
+            (parse_quote!(Vec<u8>), None),
+            (parse_quote!(bool), None),
+        ];
+        for (ty, expected) in cases {
+            assert_eq!(infer_attr_type(&ty), expected, "{}", quote!(#ty));
+        }
+    }
+
+    #[test]
+    fn rename_all_should_follow_serde_rules() {
+        let cases = [
+            ("lowercase", "user_id"),
+            ("UPPERCASE", "USER_ID"),
+            ("PascalCase", "UserId"),
+            ("camelCase", "userId"),
+            ("snake_case", "user_id"),
+            ("SCREAMING_SNAKE_CASE", "USER_ID"),
+            ("kebab-case", "user-id"),
+            ("SCREAMING-KEBAB-CASE", "USER-ID"),
+        ];
+        for (rule, expected) in cases {
+            assert_eq!(rename_field("user_id", rule).as_deref(), Some(expected));
+        }
+        assert_eq!(rename_field("user_id", "Title Case"), None);
+    }
+
+    #[test]
+    fn table_name_should_default_to_snake_case() {
+        assert_eq!(snake_case("UserOrder"), "user_order");
+        assert_eq!(snake_case("Users"), "users");
+    }
+}
@@ -0,0 +1,130 @@
+// Generated by dynamodb-tools from a table config. Do not edit.
+
+/// Names and keys of table `users`.
+pub mod users {
+    /// The base name of the table.
+    pub const TABLE_NAME: &str = "users";
+
+    /// Attribute names.
+    pub mod attrs {
+        pub const PK: &str = "pk";
+        pub const SK: &str = "sk";
+        pub const GSI1PK: &str = "gsi1pk";
+        pub const EMAIL: &str = "email";
+    }
+
+    /// Index names.
+    pub mod indexes {
+        pub const GSI1: &str = "gsi1";
+    }
+
+    /// Key of the table.
+    #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+    pub struct Key {
+        /// The `pk` attribute.
+        pub pk: String,
+        /// The `sk` attribute.
+        pub sk: String,
+    }
+
+    impl Key {
+        /// Returns the key as a DynamoDB item.
+        pub fn to_key(&self) -> ::std::collections::HashMap<String, ::aws_sdk_dynamodb::types::AttributeValue> {
+            ::std::collections::HashMap::from([
+                (attrs::PK.to_string(), ::aws_sdk_dynamodb::types::AttributeValue::S(self.pk.clone())),
+                (attrs::SK.to_string(), ::aws_sdk_dynamodb::types::AttributeValue::S(self.sk.clone())),
+            ])
+        }
+    }
+
+    /// Key of index `gsi1`.
+    #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+    pub struct Gsi1Key {
+        /// The `gsi1pk` attribute.
+        pub gsi1pk: String,
+    }
+
+    impl Gsi1Key {
+        /// Returns the key as a DynamoDB item.
+        pub fn to_key(&self) -> ::std::collections::HashMap<String, ::aws_sdk_dynamodb::types::AttributeValue> {
+            ::std::collections::HashMap::from([
+                (attrs::GSI1PK.to_string(), ::aws_sdk_dynamodb::types::AttributeValue::S(self.gsi1pk.clone())),
+            ])
+        }
+    }
+}
+
+/// Names and keys of table `order-events`.
+pub mod order_events {
+    /// The base name of the table.
+    pub const TABLE_NAME: &str = "order-events";
+
+    /// Attribute names.
+    pub mod attrs {
+        pub const ORDER_ID: &str = "orderId";
+        pub const SEQ: &str = "seq";
+        pub const TYPE: &str = "type";
+        pub const DIGEST: &str = "digest";
+        pub const EXPIRES_AT: &str = "expires_at";
+    }
+
+    /// Index names.
+    pub mod indexes {
+        pub const BY_TYPE: &str = "by_type";
+        pub const BY_DIGEST: &str = "by_digest";
+    }
+
+    /// Key of the table.
+    #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+    pub struct Key {
+        /// The `orderId` attribute.
+        pub order_id: String,
+        /// The `seq` attribute.
+        pub seq: i64,
+    }
+
+    impl Key {
+        /// Returns the key as a DynamoDB item.
+        pub fn to_key(&self) -> ::std::collections::HashMap<String, ::aws_sdk_dynamodb::types::AttributeValue> {
+            ::std::collections::HashMap::from([
+                (attrs::ORDER_ID.to_string(), ::aws_sdk_dynamodb::types::AttributeValue::S(self.order_id.clone())),
+                (attrs::SEQ.to_string(), ::aws_sdk_dynamodb::types::AttributeValue::N(self.seq.to_string())),
+            ])
+        }
+    }
+
+    /// Key of index `by_type`.
+    #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+    pub struct ByTypeKey {
+        /// The `type` attribute.
+        pub type_: String,
+        /// The `seq` attribute.
+        pub seq: i64,
+    }
+
+    impl ByTypeKey {
+        /// Returns the key as a DynamoDB item.
+        pub fn to_key(&self) -> ::std::collections::HashMap<String, ::aws_sdk_dynamodb::types::AttributeValue> {
+            ::std::collections::HashMap::from([
+                (attrs::TYPE.to_string(), ::aws_sdk_dynamodb::types::AttributeValue::S(self.type_.clone())),
+                (attrs::SEQ.to_string(), ::aws_sdk_dynamodb::types::AttributeValue::N(self.seq.to_string())),
+            ])
+        }
+    }
+
+    /// Key of index `by_digest`.
+    #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+    pub struct ByDigestKey {
+        /// The `digest` attribute.
+        pub digest: Vec<u8>,
+    }
+
+    impl ByDigestKey {
+        /// Returns the key as a DynamoDB item.
+        pub fn to_key(&self) -> ::std::collections::HashMap<String, ::aws_sdk_dynamodb::types::AttributeValue> {
+            ::std::collections::HashMap::from([
+                (attrs::DIGEST.to_string(), ::aws_sdk_dynamodb::types::AttributeValue::B(::aws_sdk_dynamodb::primitives::Blob::new(self.digest.clone()))),
+            ])
+        }
+    }
+}
@@ -0,0 +1,676 @@
+use crate::{DynamodbConnector, scan};
+use serde::Serialize;
+use serde_json::{Map, Number, Value};
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+/// Environment variable that makes [`DynamodbConnector::assert_table_snapshot`]
+/// write the golden file instead of comparing against it.
+pub const UPDATE_SNAPSHOTS_ENV: &str = "DYNAMODB_TOOLS_UPDATE_SNAPSHOTS";
+
+/// Volatile values replaced before a table is compared with its golden file.
+///
+/// Primary key attributes are never redacted, since snapshot items are sorted
+/// and matched by key; tables whose keys are random need deterministic keys in
+/// tests.
+///
+/// ```rust,ignore
+/// let redactions = Redactions::new().attribute("updated_at").uuids();
+/// connector
+///     .assert_table_snapshot_with("users", "tests/snapshots/users.json", &redactions)
+///     .await;
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Redactions {
+    attributes: Vec<String>,
+    uuids: bool,
+    timestamps: bool,
+}
+
+impl Redactions {
+    /// Creates redactions that change nothing.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Replaces the value of attribute `name` (at any depth) with `"[redacted]"`.
+    pub fn attribute(mut self, name: impl Into<String>) -> Self {
+        self.attributes.push(name.into());
+        self
+    }
+
+    /// Replaces string values holding a UUID with `"[uuid]"`.
+    pub fn uuids(mut self) -> Self {
+        self.uuids = true;
+        self
+    }
+
+    /// Replaces string values holding an RFC 3339 timestamp (e.g.
+    /// `2024-05-01T12:00:00Z`) with `"[timestamp]"`.
+    pub fn timestamps(mut self) -> Self {
+        self.timestamps = true;
+        self
+    }
+
+    // Redacts the attributes of a snapshot item except its key attributes
+    fn apply_to_item(&self, item: &mut Value, key_names: &[String]) {
+        let Value::Object(map) = item else {
+            return self.apply(item);
+        };
+        for (name, value) in map.iter_mut() {
+            if key_names.contains(name) {
+                continue;
+            }
+            if self.attributes.contains(name) {
+                *value = Value::String("[redacted]".to_string());
+            } else {
+                self.apply(value);
+            }
+        }
+    }
+
+    fn apply(&self, value: &mut Value) {
+        match value {
+            Value::Object(map) => {
+                for (name, value) in map.iter_mut() {
+                    if self.attributes.contains(name) {
+                        *value = Value::String("[redacted]".to_string());
+                    } else {
+                        self.apply(value);
+                    }
+                }
+            }
+            Value::Array(values) => values.iter_mut().for_each(|value| self.apply(value)),
+            Value::String(s) if self.uuids && is_uuid(s) => *value = "[uuid]".into(),
+            Value::String(s) if self.timestamps && is_timestamp(s) => *value = "[timestamp]".into(),
+            _ => {}
+        }
+    }
+}
+
+/// Assertions on table contents, for tests. They panic with a description of
+/// the mismatch, like `assert_eq!`.
+impl DynamodbConnector {
+    /// Asserts that table `base_name` holds an item with the given key (see
+    /// [`crate::TableHandle::get`] for `key`).
+    ///
+    /// # Panics
+    ///
+    /// If the item is missing or the table cannot be read.
+    pub async fn assert_item_exists<K: Serialize>(&self, base_name: &str, key: &K) {
+        if self.fetch_item(base_name, key).await.is_none() {
+            panic!(
+                "assertion failed: no item with key {} in table '{}'",
+                json(key),
+                base_name
+            );
+        }
+    }
+
+    /// Asserts that the item with the given key in table `base_name` equals
+    /// `expected`, attribute for attribute. Numbers compare by value.
+    ///
+    /// # Panics
+    ///
+    /// If the item is missing or differs (listing the missing, unexpected and
+    /// different attributes), or the table cannot be read.
+    pub async fn assert_item_eq<K: Serialize>(&self, base_name: &str, key: &K, expected: Value) {
+        let Some(actual) = self.fetch_item(base_name, key).await else {
+            panic!(
+                "assertion failed: no item with key {} in table '{}'",
+                json(key),
+                base_name
+            );
+        };
+        let expected = as_object(&expected);
+        let diff = diff(expected, as_object(&actual));
+        if !diff.is_empty() {
+            panic!(
+                "assertion failed: item with key {} in table '{}' differs (-expected +actual):\n{}",
+                json(key),
+                base_name,
+                diff
+            );
+        }
+    }
+
+    /// Asserts that table `base_name` holds exactly `count` items.
+    ///
+    /// # Panics
+    ///
+    /// If the count differs or the table cannot be scanned.
+    pub async fn assert_item_count(&self, base_name: &str, count: usize) {
+        let items = scan::scan_all(self.client_or_panic(), &self.table_name(base_name), 1)
+            .await
+            .unwrap_or_else(|e| panic!("failed to scan table '{}': {}", base_name, e));
+        assert_eq!(
+            items.len(),
+            count,
+            "item count of table '{}': {} expected, {} found",
+            base_name,
+            count,
+            items.len()
+        );
+    }
+
+    /// Asserts that index `index` of table `base_name` holds an item with every
+    /// attribute of `key`, which must include the index partition key (and may
+    /// include its sort key, the table key or any projected attribute).
+    ///
+    /// # Panics
+    ///
+    /// If no such item is found (listing the items under that partition key),
+    /// `key` lacks the index partition key or the index cannot be queried.
+    pub async fn assert_index_contains<K: Serialize>(&self, base_name: &str, index: &str, key: &K) {
+        let table = self
+            .table(base_name)
+            .unwrap_or_else(|e| panic!("unknown table '{}': {}", base_name, e));
+        let expected = serde_json::to_value(key)
+            .unwrap_or_else(|e| panic!("failed to convert key for index '{}': {}", index, e));
+        let expected = as_object(&expected);
+        let info = table.info();
+        let pk_name = info
+            .gsis
+            .iter()
+            .find(|gsi| gsi.name == index)
+            .map(|gsi| &gsi.pk.name)
+            .or_else(|| {
+                info.lsis
+                    .iter()
+                    .find(|lsi| lsi.name == index)
+                    .map(|lsi| &lsi.pk.name)
+            })
+            .unwrap_or_else(|| panic!("unknown index '{}' of table '{}'", index, base_name));
+        let pk = expected.get(pk_name).unwrap_or_else(|| {
+            panic!(
+                "key {} lacks partition key '{}' of index '{}'",
+                json(key),
+                pk_name,
+                index
+            )
+        });
+
+        let items: Vec<Value> = table
+            .query_builder()
+            .index(index)
+            .pk_eq(pk)
+            .collect()
+            .await
+            .unwrap_or_else(|e| panic!("failed to query index '{}': {}", index, e));
+        let found = items
+            .iter()
+            .any(|item| diff(expected, &only(as_object(item), expected)).is_empty());
+        if !found {
+            let items: Vec<String> = items.iter().map(|item| format!("  {}", item)).collect();
+            panic!(
+                "assertion failed: no item matching {} in index '{}' of table '{}'; items under {} = {}:\n{}",
+                json(key),
+                index,
+                base_name,
+                pk_name,
+                pk,
+                if items.is_empty() {
+                    "  (none)".to_string()
+                } else {
+                    items.join("\n")
+                }
+            );
+        }
+    }
+
+    /// Asserts that the items of table `base_name` match the golden file at `path`,
+    /// like [`DynamodbConnector::assert_table_snapshot_with`] without redactions.
+    ///
+    /// # Panics
+    ///
+    /// See [`DynamodbConnector::assert_table_snapshot_with`].
+    pub async fn assert_table_snapshot(&self, base_name: &str, path: impl AsRef<Path>) {
+        self.assert_table_snapshot_with(base_name, path, &Redactions::new())
+            .await
+    }
+
+    /// Asserts that the items of table `base_name` match the golden file at `path`.
+    ///
+    /// The items are scanned, converted to plain JSON, redacted (except their key
+    /// attributes) and sorted by their primary key. If the [`UPDATE_SNAPSHOTS_ENV`] environment variable is
+    /// set, the file is written (or rewritten) instead and the assertion passes.
+    ///
+    /// # Panics
+    ///
+    /// If the file is missing or its items differ (listing the missing, unexpected
+    /// and different items), `redactions` name a key attribute, or the table cannot
+    /// be scanned.
+    pub async fn assert_table_snapshot_with(
+        &self,
+        base_name: &str,
+        path: impl AsRef<Path>,
+        redactions: &Redactions,
+    ) {
+        let path = path.as_ref();
+        let key_names = self
+            .table(base_name)
+            .unwrap_or_else(|e| panic!("unknown table '{}': {}", base_name, e))
+            .info()
+            .key_names();
+        let items = scan::scan_all(self.client_or_panic(), &self.table_name(base_name), 1)
+            .await
+            .unwrap_or_else(|e| panic!("failed to scan table '{}': {}", base_name, e));
+        let actual: Vec<Value> = items
+            .into_iter()
+            .map(|item| {
+                serde_dynamo::from_item(item).unwrap_or_else(|e| {
+                    panic!("failed to convert an item of table '{}': {}", base_name, e)
+                })
+            })
+            .collect();
+        let actual = snapshot_items(actual, &key_names, redactions);
+
+        if std::env::var_os(UPDATE_SNAPSHOTS_ENV).is_some() {
+            // Serializing JSON values cannot fail
+            let content = serde_json::to_string_pretty(&actual).unwrap_or_default() + "\n";
+            if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+                fs::create_dir_all(dir).unwrap_or_else(|e| {
+                    panic!("failed to create snapshot directory {:?}: {}", dir, e)
+                });
+            }
+            fs::write(path, content)
+                .unwrap_or_else(|e| panic!("failed to write snapshot {:?}: {}", path, e));
+            println!(
+                "[INFO] Updated snapshot {:?} of table '{}'",
+                path, base_name
+            );
+            return;
+        }
+
+        let content = fs::read_to_string(path).unwrap_or_else(|e| {
+            panic!(
+                "failed to read snapshot {:?} ({}); set {}=1 to create it",
+                path, e, UPDATE_SNAPSHOTS_ENV
+            )
+        });
+        let expected: Vec<Value> = serde_json::from_str(&content)
+            .unwrap_or_else(|e| panic!("failed to parse snapshot {:?}: {}", path, e));
+        let report = snapshot_diff(&expected, &actual, &key_names);
+        if !report.is_empty() {
+            panic!(
+                "assertion failed: table '{}' differs from snapshot {:?} (-expected +actual); set {}=1 to update it:\n{}",
+                base_name, path, UPDATE_SNAPSHOTS_ENV, report
+            );
+        }
+    }
+
+    async fn fetch_item<K: Serialize>(&self, base_name: &str, key: &K) -> Option<Value> {
+        self.table(base_name)
+            .unwrap_or_else(|e| panic!("unknown table '{}': {}", base_name, e))
+            .get(key)
+            .await
+            .unwrap_or_else(|e| panic!("failed to read table '{}': {}", base_name, e))
+    }
+
+    fn client_or_panic(&self) -> &aws_sdk_dynamodb::Client {
+        self.client()
+            .unwrap_or_else(|e| panic!("no DynamoDB client: {}", e))
+    }
+
+    fn table_name(&self, base_name: &str) -> String {
+        self.get_created_table_name(base_name)
+            .unwrap_or_else(|| panic!("unknown table '{}'", base_name))
+            .to_string()
+    }
+}
+
+fn json<K: Serialize>(value: &K) -> String {
+    serde_json::to_string(value).unwrap_or_else(|_| "<unserializable>".to_string())
+}
+
+fn as_object(value: &Value) -> &Map<String, Value> {
+    value
+        .as_object()
+        .unwrap_or_else(|| panic!("expected a JSON object, got {}", value))
+}
+
+// Keeps the attributes of `item` that `filter` also has
+fn only(item: &Map<String, Value>, filter: &Map<String, Value>) -> Map<String, Value> {
+    item.iter()
+        .filter(|(name, _)| filter.contains_key(*name))
+        .map(|(name, value)| (name.clone(), value.clone()))
+        .collect()
+}
+
+// Lists the attributes that differ between `expected` and `actual`, one per line;
+// empty if they are equal
+fn diff(expected: &Map<String, Value>, actual: &Map<String, Value>) -> String {
+    let mut names: Vec<&String> = expected.keys().chain(actual.keys()).collect();
+    names.sort();
+    names.dedup();
+    let mut lines = Vec::new();
+    for name in names {
+        match (expected.get(name), actual.get(name)) {
+            (Some(e), Some(a)) if same(e, a) => {}
+            (Some(e), Some(a)) => {
+                lines.push(format!("  - {}: {}", name, e));
+                lines.push(format!("  + {}: {}", name, a));
+            }
+            (Some(e), None) => lines.push(format!("  - {}: {} (missing)", name, e)),
+            (None, Some(a)) => lines.push(format!("  + {}: {} (unexpected)", name, a)),
+            (None, None) => {}
+        }
+    }
+    lines.join("\n")
+}
+
+// Compares values, treating numbers by value (DynamoDB does not keep `1.0` vs `1`)
+fn same(expected: &Value, actual: &Value) -> bool {
+    match (expected, actual) {
+        (Value::Number(e), Value::Number(a)) => compare_numbers(e, a).is_eq(),
+        (Value::Array(e), Value::Array(a)) => {
+            e.len() == a.len() && e.iter().zip(a).all(|(e, a)| same(e, a))
+        }
+        (Value::Object(e), Value::Object(a)) => diff(e, a).is_empty(),
+        _ => expected == actual,
+    }
+}
+
+// Redacts `items` and sorts them by key. Keys stay intact, so items redacted alike
+// (e.g. with UUID keys) are still told apart when sorting and diffing
+fn snapshot_items(
+    mut items: Vec<Value>,
+    key_names: &[String],
+    redactions: &Redactions,
+) -> Vec<Value> {
+    if let Some(name) = key_names
+        .iter()
+        .find(|name| redactions.attributes.contains(name))
+    {
+        panic!(
+            "cannot redact key attribute '{}': snapshot items are matched by key",
+            name
+        );
+    }
+    for item in &mut items {
+        redactions.apply_to_item(item, key_names);
+    }
+    items.sort_by(|a, b| compare_items(a, b, key_names));
+    items
+}
+
+// Orders items by their key attributes (numbers by value), then by content
+fn compare_items(a: &Value, b: &Value, key_names: &[String]) -> Ordering {
+    key_names
+        .iter()
+        .map(|name| compare_values(&a[name], &b[name]))
+        .find(|ordering| ordering.is_ne())
+        .unwrap_or_else(|| a.to_string().cmp(&b.to_string()))
+}
+
+fn compare_values(a: &Value, b: &Value) -> Ordering {
+    match (a, b) {
+        (Value::Number(a), Value::Number(b)) => compare_numbers(a, b),
+        _ => a.to_string().cmp(&b.to_string()),
+    }
+}
+
+// Integers compare exactly (beyond 2^53 an f64 cannot tell them apart); only
+// floats fall back to f64
+fn compare_numbers(a: &Number, b: &Number) -> Ordering {
+    if let (Some(a), Some(b)) = (a.as_i64(), b.as_i64()) {
+        return a.cmp(&b);
+    }
+    if let (Some(a), Some(b)) = (a.as_u64(), b.as_u64()) {
+        return a.cmp(&b);
+    }
+    // A negative integer against one above i64::MAX
+    match (a.as_i64(), b.as_u64(), a.as_u64(), b.as_i64()) {
+        (Some(_), Some(_), _, _) => Ordering::Less,
+        (_, _, Some(_), Some(_)) => Ordering::Greater,
+        _ => {
+            let (a, b) = (
+                a.as_f64().unwrap_or_default(),
+                b.as_f64().unwrap_or_default(),
+            );
+            a.partial_cmp(&b).unwrap_or_else(|| a.total_cmp(&b))
+        }
+    }
+}
+
+// Describes the items that differ between the golden file and the table, matched
+// by key; empty if they are equal
+fn snapshot_diff(expected: &[Value], actual: &[Value], key_names: &[String]) -> String {
+    let by_key = |items: &[Value]| -> BTreeMap<String, Vec<Value>> {
+        let mut map: BTreeMap<String, Vec<Value>> = BTreeMap::new();
+        for item in items {
+            let key: Map<String, Value> = key_names
+                .iter()
+                .map(|name| (name.clone(), item[name].clone()))
+                .collect();
+            map.entry(Value::Object(key).to_string())
+                .or_default()
+                .push(item.clone());
+        }
+        map
+    };
+    let (expected, actual) = (by_key(expected), by_key(actual));
+    let mut keys: Vec<&String> = expected.keys().chain(actual.keys()).collect();
+    keys.sort();
+    keys.dedup();
+    let mut out = Vec::new();
+    for key in keys {
+        match (expected.get(key), actual.get(key)) {
+            (Some(e), Some(a)) if e.len() == 1 && a.len() == 1 => {
+                let diff = diff(as_object(&e[0]), as_object(&a[0]));
+                if !diff.is_empty() {
+                    out.push(format!("~ item {}:\n{}", key, diff));
+                }
+            }
+            (Some(e), Some(a)) => {
+                if !same(&Value::Array(e.clone()), &Value::Array(a.clone())) {
+                    out.push(format!(
+                        "~ items {}: {} expected, {} found",
+                        key,
+                        e.len(),
+                        a.len()
+                    ));
+                }
+            }
+            (Some(e), None) => out.extend(e.iter().map(|item| format!("- item {}", item))),
+            (None, Some(a)) => out.extend(a.iter().map(|item| format!("+ item {}", item))),
+            (None, None) => {}
+        }
+    }
+    out.join("\n")
+}
+
+fn is_uuid(s: &str) -> bool {
+    s.len() == 36
+        && s.char_indices().all(|(i, c)| match i {
+            8 | 13 | 18 | 23 => c == '-',
+            _ => c.is_ascii_hexdigit(),
+        })
+}
+
+// Matches `YYYY-MM-DDTHH:MM:SS` followed by optional fractional seconds and an
+// optional `Z` or `+HH:MM` offset
+fn is_timestamp(s: &str) -> bool {
+    let b = s.as_bytes();
+    let digits = |range: std::ops::Range<usize>| range.into_iter().all(|i| b[i].is_ascii_digit());
+    if b.len() < 19
+        || !(digits(0..4) && digits(5..7) && digits(8..10))
+        || !(digits(11..13) && digits(14..16) && digits(17..19))
+        || (b[4], b[7], b[13], b[16]) != (b'-', b'-', b':', b':')
+        || !matches!(b[10], b'T' | b't' | b' ')
+    {
+        return false;
+    }
+    let mut rest = &s[19..];
+    if let Some(fraction) = rest.strip_prefix('.') {
+        let len = fraction.len()
+            - fraction
+                .trim_start_matches(|c: char| c.is_ascii_digit())
+                .len();
+        if len == 0 {
+            return false;
+        }
+        rest = &fraction[len..];
+    }
+    match rest.as_bytes() {
+        [] | [b'Z' | b'z'] => true,
+        [b'+' | b'-', h1, h2, b':', m1, m2] => [h1, h2, m1, m2].iter().all(|c| c.is_ascii_digit()),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn diff_should_list_differing_attributes() {
+        let expected = json!({ "pk": "u1", "name": "Alice", "age": 30, "tags": ["a"] });
+        let actual = json!({ "pk": "u1", "name": "Bob", "age": 30.0, "email": "b@x.io" });
+        assert_eq!(
+            diff(as_object(&expected), as_object(&actual)),
+            [
+                "  + email: \"b@x.io\" (unexpected)",
+                "  - name: \"Alice\"",
+                "  + name: \"Bob\"",
+                "  - tags: [\"a\"] (missing)",
+            ]
+            .join("\n")
+        );
+        assert_eq!(diff(as_object(&expected), as_object(&expected)), "");
+    }
+
+    #[test]
+    fn redactions_should_replace_volatile_values() {
+        let mut item = json!({
+            "id": "67e55044-10b1-426f-9247-bb680e5fe0c8",
+            "created": "2024-05-01T12:00:00.123Z",
+            "updated": "2024-05-01 12:00:00+02:00",
+            "day": "2024-05-01",
+            "version": 3,
+            "history": [{ "at": "2024-05-01T12:00:00Z", "token": "abc" }],
+        });
+        Redactions::new()
+            .attribute("token")
+            .uuids()
+            .timestamps()
+            .apply(&mut item);
+        assert_eq!(
+            item,
+            json!({
+                "id": "[uuid]",
+                "created": "[timestamp]",
+                "updated": "[timestamp]",
+                "day": "2024-05-01",
+                "version": 3,
+                "history": [{ "at": "[timestamp]", "token": "[redacted]" }],
+            })
+        );
+    }
+
+    #[test]
+    fn snapshot_items_should_sort_and_diff_by_key() {
+        let keys = vec!["pk".to_string(), "sk".to_string()];
+        let mut items = vec![
+            json!({ "pk": "b", "sk": 1 }),
+            json!({ "pk": "a", "sk": 10 }),
+            json!({ "pk": "a", "sk": 9 }),
+        ];
+        items.sort_by(|a, b| compare_items(a, b, &keys));
+        assert_eq!(
+            items,
+            vec![
+                json!({ "pk": "a", "sk": 9 }),
+                json!({ "pk": "a", "sk": 10 }),
+                json!({ "pk": "b", "sk": 1 }),
+            ]
+        );
+
+        let mut actual = items.clone();
+        actual[0]["name"] = json!("x");
+        actual.pop();
+        assert_eq!(snapshot_diff(&items, &items, &keys), "");
+        assert_eq!(
+            snapshot_diff(&items, &actual, &keys),
+            [
+                "~ item {\"pk\":\"a\",\"sk\":9}:",
+                "  + name: \"x\" (unexpected)",
+                "- item {\"pk\":\"b\",\"sk\":1}",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn snapshot_keys_should_not_be_redacted() {
+        let keys = vec!["id".to_string()];
+        let redactions = Redactions::new().uuids();
+        let items = vec![
+            json!({ "id": "e2f4a2b0-8c1d-4b7e-9a55-3f1c2d4e5f60", "owner": "1d9c8e6a-0b2f-4c3d-8e7f-6a5b4c3d2e1f", "n": 2 }),
+            json!({ "id": "67e55044-10b1-426f-9247-bb680e5fe0c8", "owner": "1d9c8e6a-0b2f-4c3d-8e7f-6a5b4c3d2e1f", "n": 1 }),
+        ];
+        let expected = snapshot_items(items.clone(), &keys, &redactions);
+        assert_eq!(
+            expected,
+            vec![
+                json!({ "id": "67e55044-10b1-426f-9247-bb680e5fe0c8", "owner": "[uuid]", "n": 1 }),
+                json!({ "id": "e2f4a2b0-8c1d-4b7e-9a55-3f1c2d4e5f60", "owner": "[uuid]", "n": 2 }),
+            ]
+        );
+
+        // Scan order does not matter, and a change is reported on its own item
+        let mut actual = vec![items[1].clone(), items[0].clone()];
+        actual[0]["n"] = json!(3);
+        let actual = snapshot_items(actual, &keys, &redactions);
+        assert_eq!(
+            snapshot_diff(&expected, &actual, &keys),
+            [
+                "~ item {\"id\":\"67e55044-10b1-426f-9247-bb680e5fe0c8\"}:",
+                "  - n: 1",
+                "  + n: 3",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot redact key attribute 'id'")]
+    fn key_attributes_should_not_be_redactable() {
+        snapshot_items(
+            Vec::new(),
+            &["id".to_string()],
+            &Redactions::new().attribute("id"),
+        );
+    }
+
+    #[test]
+    fn large_integers_should_compare_exactly() {
+        // 2^53 + 1 and 2^53 are the same f64
+        assert!(!same(
+            &json!(9007199254740993u64),
+            &json!(9007199254740992u64)
+        ));
+        assert!(same(
+            &json!(9007199254740993u64),
+            &json!(9007199254740993u64)
+        ));
+        assert!(same(&json!(1), &json!(1.0)));
+        assert_eq!(
+            compare_values(&json!(-9007199254740993i64), &json!(-9007199254740992i64)),
+            Ordering::Less
+        );
+        assert_eq!(compare_values(&json!(-1), &json!(u64::MAX)), Ordering::Less);
+        assert_eq!(compare_values(&json!(2), &json!(1.5)), Ordering::Greater);
+    }
+
+    #[test]
+    fn only_should_keep_filtered_attributes() {
+        let item = json!({ "pk": "u1", "gsi1pk": "x", "name": "Alice" });
+        let filter = json!({ "gsi1pk": "x", "pk": "u1" });
+        let kept = only(as_object(&item), as_object(&filter));
+        assert!(diff(as_object(&filter), &kept).is_empty());
+    }
+}
@@ -0,0 +1,415 @@
+//! Application Auto Scaling for provisioned tables and GSIs (requires the
+//! `auto_scaling` feature).
+//!
+//! [`scaling_targets`] turns the [`AutoScaling`] settings of a [`TableInfo`] into
+//! scalable targets, and [`register_auto_scaling`] registers them through any
+//! [`AutoScalingApi`] implementation. The implementation for
+//! `aws_sdk_applicationautoscaling::Client` is used by [`crate::DynamodbConnector`];
+//! tests can provide a stub instead.
+
+use crate::error::{DynamoToolsError, Result};
+use crate::{AutoScaling, ScalingSettings, TableInfo, Throughput};
+use aws_sdk_applicationautoscaling::Client;
+use aws_sdk_applicationautoscaling::types::{
+    MetricType, PolicyType, PredefinedMetricSpecification,
+    ScalableDimension as SdkScalableDimension, ServiceNamespace,
+    TargetTrackingScalingPolicyConfiguration,
+};
+use std::future::Future;
+
+/// A DynamoDB capacity dimension that can be scaled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScalableDimension {
+    /// Read capacity of a table.
+    TableRead,
+    /// Write capacity of a table.
+    TableWrite,
+    /// Read capacity of a GSI.
+    IndexRead,
+    /// Write capacity of a GSI.
+    IndexWrite,
+}
+
+impl ScalableDimension {
+    /// The Application Auto Scaling name of the dimension
+    /// (e.g. `dynamodb:table:ReadCapacityUnits`).
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ScalableDimension::TableRead => "dynamodb:table:ReadCapacityUnits",
+            ScalableDimension::TableWrite => "dynamodb:table:WriteCapacityUnits",
+            ScalableDimension::IndexRead => "dynamodb:index:ReadCapacityUnits",
+            ScalableDimension::IndexWrite => "dynamodb:index:WriteCapacityUnits",
+        }
+    }
+
+    fn is_read(&self) -> bool {
+        matches!(
+            self,
+            ScalableDimension::TableRead | ScalableDimension::IndexRead
+        )
+    }
+}
+
+/// A scalable target together with its target tracking policy.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScalingTarget {
+    /// The resource ID, e.g. `table/users-xxx` or `table/users-xxx/index/gsi1`.
+    pub resource_id: String,
+    /// The scaled capacity dimension.
+    pub dimension: ScalableDimension,
+    /// Minimum capacity units.
+    pub min_capacity: i32,
+    /// Maximum capacity units.
+    pub max_capacity: i32,
+    /// Target utilization in percent.
+    pub target_utilization: f64,
+}
+
+impl ScalingTarget {
+    /// The name of the target tracking policy for this target.
+    pub fn policy_name(&self) -> String {
+        let kind = if self.dimension.is_read() {
+            "read"
+        } else {
+            "write"
+        };
+        format!("{}-{}-scaling", self.resource_id.replace('/', "-"), kind)
+    }
+}
+
+/// The Application Auto Scaling calls needed to set up scaling.
+pub trait AutoScalingApi {
+    /// Registers `target` as a scalable target with its min/max capacity.
+    fn register_scalable_target(
+        &self,
+        target: &ScalingTarget,
+    ) -> impl Future<Output = Result<()>> + Send;
+
+    /// Puts the target tracking scaling policy of `target`.
+    fn put_scaling_policy(&self, target: &ScalingTarget)
+    -> impl Future<Output = Result<()>> + Send;
+
+    /// Deregisters `target`, which also deletes its scaling policy.
+    fn deregister_scalable_target(
+        &self,
+        target: &ScalingTarget,
+    ) -> impl Future<Output = Result<()>> + Send;
+}
+
+impl AutoScalingApi for Client {
+    async fn register_scalable_target(&self, target: &ScalingTarget) -> Result<()> {
+        self.register_scalable_target()
+            .service_namespace(ServiceNamespace::Dynamodb)
+            .resource_id(&target.resource_id)
+            .scalable_dimension(SdkScalableDimension::from(target.dimension.as_str()))
+            .min_capacity(target.min_capacity)
+            .max_capacity(target.max_capacity)
+            .send()
+            .await
+            .map_err(|e| DynamoToolsError::AutoScaling(target.resource_id.clone(), e.into()))?;
+        Ok(())
+    }
+
+    async fn put_scaling_policy(&self, target: &ScalingTarget) -> Result<()> {
+        let to_err = |e: aws_sdk_applicationautoscaling::error::BuildError| {
+            DynamoToolsError::AutoScaling(target.resource_id.clone(), e.into())
+        };
+        let metric = if target.dimension.is_read() {
+            MetricType::DynamoDbReadCapacityUtilization
+        } else {
+            MetricType::DynamoDbWriteCapacityUtilization
+        };
+        let config = TargetTrackingScalingPolicyConfiguration::builder()
+            .target_value(target.target_utilization)
+            .predefined_metric_specification(
+                PredefinedMetricSpecification::builder()
+                    .predefined_metric_type(metric)
+                    .build()
+                    .map_err(to_err)?,
+            )
+            .build()
+            .map_err(to_err)?;
+
+        self.put_scaling_policy()
+            .policy_name(target.policy_name())
+            .service_namespace(ServiceNamespace::Dynamodb)
+            .resource_id(&target.resource_id)
+            .scalable_dimension(SdkScalableDimension::from(target.dimension.as_str()))
+            .policy_type(PolicyType::TargetTrackingScaling)
+            .target_tracking_scaling_policy_configuration(config)
+            .send()
+            .await
+            .map_err(|e| DynamoToolsError::AutoScaling(target.resource_id.clone(), e.into()))?;
+        Ok(())
+    }
+
+    async fn deregister_scalable_target(&self, target: &ScalingTarget) -> Result<()> {
+        self.deregister_scalable_target()
+            .service_namespace(ServiceNamespace::Dynamodb)
+            .resource_id(&target.resource_id)
+            .scalable_dimension(SdkScalableDimension::from(target.dimension.as_str()))
+            .send()
+            .await
+            .map_err(|e| DynamoToolsError::AutoScaling(target.resource_id.clone(), e.into()))?;
+        Ok(())
+    }
+}
+
+/// Returns the scaling targets described by the auto scaling settings of
+/// `info` and its GSIs, for the table created as `table_name`.
+pub fn scaling_targets(table_name: &str, info: &TableInfo) -> Vec<ScalingTarget> {
+    let mut targets = Vec::new();
+    let table_resource = format!("table/{}", table_name);
+    if let Some(ref scaling) = info.auto_scaling {
+        push_targets(
+            &mut targets,
+            &table_resource,
+            scaling,
+            (ScalableDimension::TableRead, ScalableDimension::TableWrite),
+        );
+    }
+    for gsi in &info.gsis {
+        if let Some(ref scaling) = gsi.auto_scaling {
+            push_targets(
+                &mut targets,
+                &format!("{}/index/{}", table_resource, gsi.name),
+                scaling,
+                (ScalableDimension::IndexRead, ScalableDimension::IndexWrite),
+            );
+        }
+    }
+    targets
+}
+
+fn push_targets(
+    targets: &mut Vec<ScalingTarget>,
+    resource_id: &str,
+    scaling: &AutoScaling,
+    (read, write): (ScalableDimension, ScalableDimension),
+) {
+    let settings: [(&Option<ScalingSettings>, ScalableDimension); 2] =
+        [(&scaling.read, read), (&scaling.write, write)];
+    for (settings, dimension) in settings {
+        if let Some(settings) = settings {
+            targets.push(ScalingTarget {
+                resource_id: resource_id.to_string(),
+                dimension,
+                min_capacity: settings.min,
+                max_capacity: settings.max,
+                target_utilization: settings.target_utilization,
+            });
+        }
+    }
+}
+
+/// Checks that the table (and every GSI) with auto scaling settings uses
+/// provisioned throughput, since on-demand capacity cannot be scaled.
+///
+/// # Errors
+///
+/// Returns `Err` ([`DynamoToolsError::AutoScaling`]) naming the first table or GSI
+/// with auto scaling settings but no provisioned throughput.
+pub fn check_provisioned(info: &TableInfo) -> Result<()> {
+    let provisioned = |throughput: &Option<Throughput>| {
+        matches!(throughput, Some(Throughput::Provisioned { .. }))
+    };
+    let scaled = info.auto_scaling.is_some() || info.gsis.iter().any(|g| g.auto_scaling.is_some());
+    if scaled && !provisioned(&info.throughput) {
+        return Err(DynamoToolsError::AutoScaling(
+            info.table_name.clone(),
+            "auto scaling requires provisioned throughput, but the table is on-demand".into(),
+        ));
+    }
+    for gsi in &info.gsis {
+        if gsi.auto_scaling.is_some() && !provisioned(&gsi.throughput) {
+            return Err(DynamoToolsError::AutoScaling(
+                format!("{}/index/{}", info.table_name, gsi.name),
+                "auto scaling requires provisioned throughput on the GSI".into(),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Registers scalable targets and target tracking policies for the table created
+/// as `table_name` from `info`. Returns the number of configured targets.
+///
+/// # Errors
+///
+/// Returns `Err` ([`DynamoToolsError::AutoScaling`]) if the table or a scaled GSI
+/// is not provisioned (see [`check_provisioned`]), before any call is made, or if
+/// any call fails.
+pub async fn register_auto_scaling(
+    api: &impl AutoScalingApi,
+    table_name: &str,
+    info: &TableInfo,
+) -> Result<usize> {
+    check_provisioned(info)?;
+    let targets = scaling_targets(table_name, info);
+    for target in &targets {
+        api.register_scalable_target(target).await?;
+        api.put_scaling_policy(target).await?;
+        println!(
+            "[INFO] Configured auto scaling of {} for '{}' ({}-{} units, {}% target)",
+            target.dimension.as_str(),
+            target.resource_id,
+            target.min_capacity,
+            target.max_capacity,
+            target.target_utilization
+        );
+    }
+    Ok(targets.len())
+}
+
+/// Deregisters the scalable targets (and so their policies) registered by
+/// [`register_auto_scaling`] for the table created as `table_name` from `info`.
+/// Every target is attempted. Returns the number of deregistered targets.
+///
+/// # Errors
+///
+/// Returns `Err` ([`DynamoToolsError::AutoScaling`]) with the first failure.
+pub async fn deregister_auto_scaling(
+    api: &impl AutoScalingApi,
+    table_name: &str,
+    info: &TableInfo,
+) -> Result<usize> {
+    let mut first_error = None;
+    let mut count = 0;
+    for target in scaling_targets(table_name, info) {
+        match api.deregister_scalable_target(&target).await {
+            Ok(()) => {
+                count += 1;
+                println!(
+                    "[INFO] Deregistered auto scaling of {} for '{}'",
+                    target.dimension.as_str(),
+                    target.resource_id
+                );
+            }
+            Err(e) => {
+                eprintln!("[ERROR] {}", e);
+                first_error.get_or_insert(e);
+            }
+        }
+    }
+    match first_error {
+        Some(e) => Err(e),
+        None => Ok(count),
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    // Records the calls made through it; shared with the connector tests
+    #[derive(Default)]
+    pub(crate) struct StubApi {
+        pub(crate) calls: Mutex<Vec<String>>,
+    }
+
+    impl AutoScalingApi for StubApi {
+        async fn register_scalable_target(&self, target: &ScalingTarget) -> Result<()> {
+            self.calls.lock().unwrap().push(format!(
+                "register {} {} {}-{}",
+                target.resource_id,
+                target.dimension.as_str(),
+                target.min_capacity,
+                target.max_capacity
+            ));
+            Ok(())
+        }
+
+        async fn put_scaling_policy(&self, target: &ScalingTarget) -> Result<()> {
+            self.calls.lock().unwrap().push(format!(
+                "policy {} {}",
+                target.policy_name(),
+                target.target_utilization
+            ));
+            Ok(())
+        }
+
+        async fn deregister_scalable_target(&self, target: &ScalingTarget) -> Result<()> {
+            self.calls.lock().unwrap().push(format!(
+                "deregister {} {}",
+                target.resource_id,
+                target.dimension.as_str()
+            ));
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn auto_scaling_should_register_table_and_gsi_targets() {
+        let info = TableInfo::load(
+            r#"
+table_name: users
+pk:
+  name: pk
+  type: S
+throughput: { read: 5, write: 5 }
+auto_scaling:
+  read: { min: 5, max: 100, target_utilization: 60 }
+gsis:
+  - name: gsi1
+    pk:
+      name: gsi1pk
+      type: S
+    throughput: { read: 5, write: 5 }
+    auto_scaling:
+      write: { min: 1, max: 10 }
+"#,
+        )
+        .unwrap();
+
+        let api = StubApi::default();
+        let count = register_auto_scaling(&api, "users-1", &info).await.unwrap();
+        assert_eq!(count, 2);
+        assert_eq!(
+            *api.calls.lock().unwrap(),
+            vec![
+                "register table/users-1 dynamodb:table:ReadCapacityUnits 5-100",
+                "policy table-users-1-read-scaling 60",
+                "register table/users-1/index/gsi1 dynamodb:index:WriteCapacityUnits 1-10",
+                "policy table-users-1-index-gsi1-write-scaling 70",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn auto_scaling_should_require_provisioned_throughput() {
+        let mut info = TableInfo::load(
+            r#"
+table_name: users
+pk:
+  name: pk
+  type: S
+auto_scaling:
+  read: { min: 5, max: 100 }
+"#,
+        )
+        .unwrap();
+        let api = StubApi::default();
+        let err = register_auto_scaling(&api, "users-1", &info)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, DynamoToolsError::AutoScaling(ref name, _) if name == "users"));
+        assert!(api.calls.lock().unwrap().is_empty());
+
+        info.throughput = Some(Throughput::Provisioned { read: 5, write: 5 });
+        assert_eq!(
+            register_auto_scaling(&api, "users-1", &info).await.unwrap(),
+            1
+        );
+        assert_eq!(
+            deregister_auto_scaling(&api, "users-1", &info)
+                .await
+                .unwrap(),
+            1
+        );
+        assert_eq!(
+            api.calls.lock().unwrap().last().unwrap(),
+            "deregister table/users-1 dynamodb:table:ReadCapacityUnits"
+        );
+    }
+}
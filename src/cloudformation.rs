@@ -0,0 +1,698 @@
+use crate::config::defined_attr;
+use crate::error::{DynamoToolsError, Result};
+use crate::{
+    AttrType, Encryption, IndexProjection, StreamViewType, TableAttr, TableClass, TableConfig,
+    TableGsi, TableInfo, TableLsi, Throughput, WarmThroughput,
+};
+use aws_sdk_dynamodb::operation::create_table::CreateTableInput;
+use aws_sdk_dynamodb::types::{
+    BillingMode, KeySchemaElement, OnDemandThroughput, Projection, ProvisionedThroughput,
+    WarmThroughput as SdkWarmThroughput,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value, json};
+use std::collections::BTreeMap;
+
+/// Text formats of generated infrastructure templates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TemplateFormat {
+    /// YAML, the usual format of hand-written CloudFormation and SAM templates.
+    Yaml,
+    /// JSON.
+    Json,
+}
+
+impl TableConfig {
+    /// Renders every table as an `AWS::DynamoDB::Table` resource of a CloudFormation
+    /// template. The resources can also be pasted into the `Resources` of a SAM template.
+    ///
+    /// Each resource gets the logical ID `<PascalCaseBaseName>Table` and the base
+    /// name as `TableName`. Key schema, attribute definitions, indexes, billing,
+    /// throughput, TTL, streams, encryption, table class, deletion protection,
+    /// point-in-time recovery and the merged [`TableConfig::table_tags`] are included.
+    /// Replicas are left out, as they need an `AWS::DynamoDB::GlobalTable` instead.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if a table cannot be converted or the template cannot be rendered
+    /// ([`DynamoToolsError::TemplateRender`]).
+    pub fn to_cloudformation(&self, format: TemplateFormat) -> Result<String> {
+        let mut resources = Map::new();
+        for table in &self.tables {
+            resources.insert(
+                logical_id(&table.table_name),
+                cloudformation_resource(self, table)?,
+            );
+        }
+        let template = json!({
+            "AWSTemplateFormatVersion": "2010-09-09",
+            "Resources": resources,
+        });
+        let render_err =
+            |e: &dyn std::fmt::Display| DynamoToolsError::TemplateRender(e.to_string());
+        match format {
+            TemplateFormat::Json => {
+                serde_json::to_string_pretty(&template).map_err(|e| render_err(&e))
+            }
+            TemplateFormat::Yaml => serde_yml::to_string(&template).map_err(|e| render_err(&e)),
+        }
+    }
+}
+
+impl TableInfo {
+    /// Imports the `AWS::DynamoDB::Table` resources of a CloudFormation (or SAM)
+    /// template in YAML or JSON, so tests can use the deployed schema.
+    ///
+    /// The `TableName` becomes the base name, falling back to the logical ID when
+    /// it is missing or not a literal (e.g. `!Sub`). Other properties given through
+    /// intrinsic functions are ignored, except capacity values and KMS keys.
+    /// Attribute definitions that are not primary keys end up in [`TableInfo::attrs`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` ([`DynamoToolsError::TemplateParse`]) if the template cannot be
+    /// parsed, a table has no usable key schema, a capacity value is not a literal
+    /// number (e.g. `!Ref ReadCapacity`) or a KMS key is not a literal string.
+    pub fn from_cloudformation(template: &str) -> Result<Vec<TableInfo>> {
+        let template: serde_yml::Value = serde_yml::from_str(template)
+            .map_err(|e| DynamoToolsError::TemplateParse(e.to_string()))?;
+        let template = literal_json(template);
+        let Some(resources) = template.get("Resources").and_then(Value::as_object) else {
+            return Ok(Vec::new());
+        };
+        resources
+            .iter()
+            .filter(|(_, r)| r.get("Type").and_then(Value::as_str) == Some("AWS::DynamoDB::Table"))
+            .map(|(id, r)| import_table(id, r.get("Properties").unwrap_or(&Value::Null)))
+            .collect()
+    }
+}
+
+// Converts YAML to JSON, turning the short form of intrinsic functions into their
+// JSON form, e.g. `!Ref Env` into `{"Ref": "Env"}` and `!Sub ...` into `{"Fn::Sub": ...}`
+fn literal_json(value: serde_yml::Value) -> Value {
+    match value {
+        serde_yml::Value::Null => Value::Null,
+        serde_yml::Value::Tagged(tagged) => {
+            let tag = tagged.tag().to_string();
+            let function = match tag.trim_start_matches('!') {
+                "Ref" => "Ref".to_string(),
+                name => format!("Fn::{}", name),
+            };
+            json!({ function: literal_json(tagged.value().clone()) })
+        }
+        serde_yml::Value::Bool(b) => Value::Bool(b),
+        serde_yml::Value::Number(n) => n.as_i64().map_or_else(|| n.as_f64().into(), Value::from),
+        serde_yml::Value::String(s) => Value::String(s),
+        serde_yml::Value::Sequence(seq) => seq.into_iter().map(literal_json).collect(),
+        serde_yml::Value::Mapping(map) => {
+            map.into_iter().map(|(k, v)| (k, literal_json(v))).collect()
+        }
+    }
+}
+
+fn import_table(logical_id: &str, props: &Value) -> Result<TableInfo> {
+    let name = props
+        .get("TableName")
+        .and_then(Value::as_str)
+        .unwrap_or(logical_id);
+    let mut defs = BTreeMap::new();
+    for def in array(props, "AttributeDefinitions") {
+        if let (Some(name), Some(attr_type)) =
+            (string(def, "AttributeName"), string(def, "AttributeType"))
+        {
+            defs.insert(name.to_string(), AttrType::parse(attr_type)?);
+        }
+    }
+    let (pk, sk) = import_keys(props, &defs)?;
+    let pk = pk.ok_or_else(|| {
+        DynamoToolsError::TemplateParse(format!("table '{}' has no HASH key", name))
+    })?;
+    let mut info = TableInfo::new(name, pk, sk);
+
+    for gsi in array(props, "GlobalSecondaryIndexes") {
+        let (pk, sk) = import_keys(gsi, &defs)?;
+        let (projection, attrs) = import_projection(gsi);
+        info.gsis.push(TableGsi {
+            name: string(gsi, "IndexName").unwrap_or_default().to_string(),
+            pk: pk.ok_or_else(|| {
+                DynamoToolsError::TemplateParse(format!(
+                    "a GSI of table '{}' has no HASH key",
+                    name
+                ))
+            })?,
+            sk,
+            attrs,
+            projection,
+            throughput: import_throughput(name, gsi)?,
+            warm_throughput: import_warm_throughput(name, gsi)?,
+            auto_scaling: None,
+        });
+    }
+    for lsi in array(props, "LocalSecondaryIndexes") {
+        let (_, sk) = import_keys(lsi, &defs)?;
+        let (projection, attrs) = import_projection(lsi);
+        info.lsis.push(TableLsi {
+            name: string(lsi, "IndexName").unwrap_or_default().to_string(),
+            pk: info.pk.clone(),
+            sk: sk.ok_or_else(|| {
+                DynamoToolsError::TemplateParse(format!(
+                    "an LSI of table '{}' has no RANGE key",
+                    name
+                ))
+            })?,
+            attrs,
+            projection,
+        });
+    }
+    let key_names = info.key_names();
+    info.attrs = defs
+        .into_iter()
+        .filter(|(name, _)| !key_names.contains(name))
+        .map(|(name, attr_type)| TableAttr { name, attr_type })
+        .collect();
+
+    info.throughput = import_throughput(name, props)?;
+    info.warm_throughput = import_warm_throughput(name, props)?;
+    if let Some(ttl) = props.get("TimeToLiveSpecification")
+        && flag(ttl, "Enabled")
+    {
+        info.ttl_attribute = string(ttl, "AttributeName").map(str::to_string);
+    }
+    info.stream_view_type = props
+        .get("StreamSpecification")
+        .and_then(|s| string(s, "StreamViewType"))
+        .and_then(|v| serde_json::from_value::<StreamViewType>(json!(v)).ok());
+    if let Some(sse) = props.get("SSESpecification")
+        && flag(sse, "SSEEnabled")
+    {
+        info.encryption = Some(Encryption {
+            kms_key_id: key_id(name, sse, "KMSMasterKeyId")?,
+        });
+    }
+    info.table_class = string(props, "TableClass")
+        .and_then(|v| serde_json::from_value::<TableClass>(json!(v)).ok());
+    info.deletion_protection = flag(props, "DeletionProtectionEnabled");
+    info.point_in_time_recovery = props
+        .get("PointInTimeRecoverySpecification")
+        .is_some_and(|p| flag(p, "PointInTimeRecoveryEnabled"));
+    for tag in array(props, "Tags") {
+        if let (Some(key), Some(value)) = (string(tag, "Key"), string(tag, "Value")) {
+            info.tags.insert(key.to_string(), value.to_string());
+        }
+    }
+    Ok(info)
+}
+
+fn array<'a>(value: &'a Value, key: &str) -> &'a [Value] {
+    value
+        .get(key)
+        .and_then(Value::as_array)
+        .map(Vec::as_slice)
+        .unwrap_or_default()
+}
+
+fn string<'a>(value: &'a Value, key: &str) -> Option<&'a str> {
+    value.get(key).and_then(Value::as_str)
+}
+
+// A KMS key given through an intrinsic function cannot be dropped silently either,
+// as that would turn a customer managed key into the AWS managed one
+fn key_id(table: &str, value: &Value, key: &str) -> Result<Option<String>> {
+    match value.get(key) {
+        None | Some(Value::Null) => Ok(None),
+        Some(Value::String(s)) => Ok(Some(s.clone())),
+        Some(v) => Err(DynamoToolsError::TemplateParse(format!(
+            "table '{}': {} must be a literal string, got {}",
+            table, key, v
+        ))),
+    }
+}
+
+// CloudFormation accepts numbers and booleans as strings too. Capacity values are
+// the only numbers imported, and silently dropping one would change the billing mode.
+fn number(table: &str, value: &Value, key: &str) -> Result<Option<i64>> {
+    let Some(v) = value.get(key) else {
+        return Ok(None);
+    };
+    let number = match v {
+        Value::String(s) => s.parse().ok(),
+        v => v.as_i64(),
+    };
+    number.map(Some).ok_or_else(|| {
+        DynamoToolsError::TemplateParse(format!(
+            "table '{}': {} must be a literal number, got {}",
+            table, key, v
+        ))
+    })
+}
+
+fn flag(value: &Value, key: &str) -> bool {
+    match value.get(key) {
+        Some(Value::Bool(b)) => *b,
+        Some(Value::String(s)) => s.eq_ignore_ascii_case("true"),
+        _ => false,
+    }
+}
+
+fn import_keys(
+    value: &Value,
+    defs: &BTreeMap<String, AttrType>,
+) -> Result<(Option<TableAttr>, Option<TableAttr>)> {
+    let (mut pk, mut sk) = (None, None);
+    for key in array(value, "KeySchema") {
+        let Some(name) = string(key, "AttributeName") else {
+            continue;
+        };
+        match string(key, "KeyType") {
+            Some("HASH") => pk = Some(defined_attr(defs, name)?),
+            Some("RANGE") => sk = Some(defined_attr(defs, name)?),
+            _ => {}
+        }
+    }
+    Ok((pk, sk))
+}
+
+fn import_projection(index: &Value) -> (Option<IndexProjection>, Vec<String>) {
+    let Some(projection) = index.get("Projection") else {
+        return (Some(IndexProjection::KeysOnly), Vec::new());
+    };
+    let projection_type = string(projection, "ProjectionType")
+        .and_then(|v| serde_json::from_value(json!(v)).ok())
+        .unwrap_or(IndexProjection::KeysOnly);
+    let attrs = array(projection, "NonKeyAttributes")
+        .iter()
+        .filter_map(Value::as_str)
+        .map(str::to_string)
+        .collect();
+    (Some(projection_type), attrs)
+}
+
+fn import_throughput(table: &str, value: &Value) -> Result<Option<Throughput>> {
+    if let Some(pt) = value.get("ProvisionedThroughput")
+        && let (Some(read), Some(write)) = (
+            number(table, pt, "ReadCapacityUnits")?,
+            number(table, pt, "WriteCapacityUnits")?,
+        )
+    {
+        return Ok(Some(Throughput::Provisioned { read, write }));
+    }
+    let Some(od) = value.get("OnDemandThroughput") else {
+        return Ok(None);
+    };
+    Ok(Some(Throughput::OnDemand {
+        max_read: number(table, od, "MaxReadRequestUnits")?,
+        max_write: number(table, od, "MaxWriteRequestUnits")?,
+    }))
+}
+
+fn import_warm_throughput(table: &str, value: &Value) -> Result<Option<WarmThroughput>> {
+    let Some(warm) = value.get("WarmThroughput") else {
+        return Ok(None);
+    };
+    Ok(Some(WarmThroughput {
+        read_units_per_second: number(table, warm, "ReadUnitsPerSecond")?,
+        write_units_per_second: number(table, warm, "WriteUnitsPerSecond")?,
+    }))
+}
+
+/// Converts a table base name to a CloudFormation logical ID, e.g.
+/// `user_events` becomes `UserEventsTable`.
+pub(crate) fn logical_id(table_name: &str) -> String {
+    let mut id: String = table_name
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            chars
+                .next()
+                .map(|first| first.to_ascii_uppercase().to_string() + chars.as_str())
+                .unwrap_or_default()
+        })
+        .collect();
+    id.push_str("Table");
+    id
+}
+
+fn cloudformation_resource(config: &TableConfig, table: &TableInfo) -> Result<Value> {
+    let input = CreateTableInput::try_from(table.clone())?;
+    let mut props = Map::new();
+    props.insert("TableName".into(), json!(table.table_name));
+    props.insert(
+        "AttributeDefinitions".into(),
+        input
+            .attribute_definitions()
+            .iter()
+            .map(|attr| {
+                json!({
+                    "AttributeName": attr.attribute_name(),
+                    "AttributeType": attr.attribute_type().as_str(),
+                })
+            })
+            .collect(),
+    );
+    props.insert("KeySchema".into(), key_schema(input.key_schema()));
+
+    let billing_mode = match input.provisioned_throughput() {
+        Some(_) => BillingMode::Provisioned,
+        None => input
+            .billing_mode()
+            .cloned()
+            .unwrap_or(BillingMode::PayPerRequest),
+    };
+    props.insert("BillingMode".into(), json!(billing_mode.as_str()));
+    insert_throughput(
+        &mut props,
+        input.provisioned_throughput(),
+        input.on_demand_throughput(),
+        input.warm_throughput(),
+    );
+
+    if !input.global_secondary_indexes().is_empty() {
+        let gsis = input
+            .global_secondary_indexes()
+            .iter()
+            .map(|gsi| {
+                let mut index = index(gsi.index_name(), gsi.key_schema(), gsi.projection());
+                insert_throughput(
+                    &mut index,
+                    gsi.provisioned_throughput(),
+                    gsi.on_demand_throughput(),
+                    gsi.warm_throughput(),
+                );
+                Value::Object(index)
+            })
+            .collect();
+        props.insert("GlobalSecondaryIndexes".into(), gsis);
+    }
+    if !input.local_secondary_indexes().is_empty() {
+        let lsis = input
+            .local_secondary_indexes()
+            .iter()
+            .map(|lsi| Value::Object(index(lsi.index_name(), lsi.key_schema(), lsi.projection())))
+            .collect();
+        props.insert("LocalSecondaryIndexes".into(), lsis);
+    }
+
+    if let Some(ref attribute) = table.ttl_attribute {
+        props.insert(
+            "TimeToLiveSpecification".into(),
+            json!({ "AttributeName": attribute, "Enabled": true }),
+        );
+    }
+    if let Some(view_type) = input
+        .stream_specification()
+        .and_then(|s| s.stream_view_type())
+    {
+        props.insert(
+            "StreamSpecification".into(),
+            json!({ "StreamViewType": view_type.as_str() }),
+        );
+    }
+    if let Some(sse) = input.sse_specification() {
+        let mut spec = Map::new();
+        spec.insert("SSEEnabled".into(), json!(true));
+        if let Some(sse_type) = sse.sse_type() {
+            spec.insert("SSEType".into(), json!(sse_type.as_str()));
+        }
+        if let Some(key_id) = sse.kms_master_key_id() {
+            spec.insert("KMSMasterKeyId".into(), json!(key_id));
+        }
+        props.insert("SSESpecification".into(), Value::Object(spec));
+    }
+    if let Some(table_class) = input.table_class() {
+        props.insert("TableClass".into(), json!(table_class.as_str()));
+    }
+    if table.deletion_protection {
+        props.insert("DeletionProtectionEnabled".into(), json!(true));
+    }
+    if table.point_in_time_recovery {
+        props.insert(
+            "PointInTimeRecoverySpecification".into(),
+            json!({ "PointInTimeRecoveryEnabled": true }),
+        );
+    }
+    let tags = config.table_tags(table);
+    if !tags.is_empty() {
+        props.insert(
+            "Tags".into(),
+            tags.iter()
+                .map(|(key, value)| json!({ "Key": key, "Value": value }))
+                .collect(),
+        );
+    }
+
+    Ok(json!({
+        "Type": "AWS::DynamoDB::Table",
+        "Properties": props,
+    }))
+}
+
+fn key_schema(elements: &[KeySchemaElement]) -> Value {
+    elements
+        .iter()
+        .map(|k| {
+            json!({
+                "AttributeName": k.attribute_name(),
+                "KeyType": k.key_type().as_str(),
+            })
+        })
+        .collect()
+}
+
+fn index(
+    name: &str,
+    elements: &[KeySchemaElement],
+    projection: Option<&Projection>,
+) -> Map<String, Value> {
+    let mut index = Map::new();
+    index.insert("IndexName".into(), json!(name));
+    index.insert("KeySchema".into(), key_schema(elements));
+    if let Some(projection) = projection {
+        let mut spec = Map::new();
+        if let Some(projection_type) = projection.projection_type() {
+            spec.insert("ProjectionType".into(), json!(projection_type.as_str()));
+        }
+        if !projection.non_key_attributes().is_empty() {
+            spec.insert(
+                "NonKeyAttributes".into(),
+                json!(projection.non_key_attributes()),
+            );
+        }
+        index.insert("Projection".into(), Value::Object(spec));
+    }
+    index
+}
+
+fn insert_throughput(
+    props: &mut Map<String, Value>,
+    provisioned: Option<&ProvisionedThroughput>,
+    on_demand: Option<&OnDemandThroughput>,
+    warm: Option<&SdkWarmThroughput>,
+) {
+    if let Some(pt) = provisioned {
+        props.insert(
+            "ProvisionedThroughput".into(),
+            json!({
+                "ReadCapacityUnits": pt.read_capacity_units(),
+                "WriteCapacityUnits": pt.write_capacity_units(),
+            }),
+        );
+    }
+    if let Some(od) = on_demand {
+        let mut spec = Map::new();
+        if let Some(units) = od.max_read_request_units() {
+            spec.insert("MaxReadRequestUnits".into(), json!(units));
+        }
+        if let Some(units) = od.max_write_request_units() {
+            spec.insert("MaxWriteRequestUnits".into(), json!(units));
+        }
+        props.insert("OnDemandThroughput".into(), Value::Object(spec));
+    }
+    if let Some(warm) = warm {
+        let mut spec = Map::new();
+        if let Some(units) = warm.read_units_per_second() {
+            spec.insert("ReadUnitsPerSecond".into(), json!(units));
+        }
+        if let Some(units) = warm.write_units_per_second() {
+            spec.insert("WriteUnitsPerSecond".into(), json!(units));
+        }
+        props.insert("WarmThroughput".into(), Value::Object(spec));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn logical_id_should_be_pascal_case() {
+        assert_eq!(logical_id("users"), "UsersTable");
+        assert_eq!(logical_id("user_events-v2"), "UserEventsV2Table");
+    }
+
+    #[test]
+    fn config_should_render_cloudformation() {
+        let mut config = TableConfig::load_from_file("fixtures/dev.yml").unwrap();
+        config
+            .tags
+            .insert("team".to_string(), "platform".to_string());
+        config.tables[0].ttl_attribute = Some("expires_at".to_string());
+        config.tables[0].point_in_time_recovery = true;
+
+        let json = config.to_cloudformation(TemplateFormat::Json).unwrap();
+        let template: Value = serde_json::from_str(&json).unwrap();
+        let props = &template["Resources"]["UsersTable"]["Properties"];
+        assert_eq!(
+            template["Resources"]["UsersTable"]["Type"],
+            "AWS::DynamoDB::Table"
+        );
+        assert_eq!(props["TableName"], "users");
+        assert_eq!(props["BillingMode"], "PAY_PER_REQUEST");
+        assert_eq!(
+            props["KeySchema"],
+            json!([
+                { "AttributeName": "pk", "KeyType": "HASH" },
+                { "AttributeName": "sk", "KeyType": "RANGE" },
+            ])
+        );
+        assert_eq!(props["AttributeDefinitions"].as_array().unwrap().len(), 5);
+        assert_eq!(
+            props["GlobalSecondaryIndexes"][0]["Projection"],
+            json!({ "ProjectionType": "INCLUDE", "NonKeyAttributes": ["pk"] })
+        );
+        assert_eq!(
+            props["LocalSecondaryIndexes"][0]["Projection"],
+            json!({ "ProjectionType": "ALL" })
+        );
+        assert_eq!(
+            props["TimeToLiveSpecification"],
+            json!({ "AttributeName": "expires_at", "Enabled": true })
+        );
+        assert_eq!(
+            props["PointInTimeRecoverySpecification"]["PointInTimeRecoveryEnabled"],
+            true
+        );
+        assert_eq!(
+            props["Tags"],
+            json!([{ "Key": "team", "Value": "platform" }])
+        );
+
+        let yaml = config.to_cloudformation(TemplateFormat::Yaml).unwrap();
+        let from_yaml: Value = serde_yml::from_str(&yaml).unwrap();
+        assert_eq!(from_yaml, template);
+    }
+
+    #[test]
+    fn exported_template_should_import_back() {
+        let mut config = TableConfig::load_from_file("fixtures/dev.yml").unwrap();
+        config.tables[0].ttl_attribute = Some("expires_at".to_string());
+        config.tables[0].throughput = Some(Throughput::Provisioned { read: 5, write: 5 });
+        let template = config.to_cloudformation(TemplateFormat::Yaml).unwrap();
+
+        let tables = TableInfo::from_cloudformation(&template).unwrap();
+        assert_eq!(tables.len(), 1);
+        let info = &tables[0];
+        assert_eq!(info.table_name, "users");
+        assert_eq!(info.key_names(), vec!["pk".to_string(), "sk".to_string()]);
+        assert_eq!(info.attrs.len(), 3);
+        assert_eq!(info.gsis[0].projection, Some(IndexProjection::Include));
+        assert_eq!(info.gsis[0].attrs, vec!["pk".to_string()]);
+        assert_eq!(info.lsis[0].projection, Some(IndexProjection::All));
+        assert_eq!(info.lsis[0].sk.name, "lsi1sk");
+        assert_eq!(info.ttl_attribute.as_deref(), Some("expires_at"));
+        assert_eq!(
+            info.throughput,
+            Some(Throughput::Provisioned { read: 5, write: 5 })
+        );
+
+        // Re-exporting the imported schema gives the same template
+        let reimported = TableConfig {
+            tables: tables.clone(),
+            ..config
+        };
+        assert_eq!(
+            reimported.to_cloudformation(TemplateFormat::Yaml).unwrap(),
+            template
+        );
+    }
+
+    #[test]
+    fn intrinsic_functions_should_be_ignored_on_import() {
+        let template = r#"
+Parameters:
+  Env:
+    Type: String
+Resources:
+  OrdersTable:
+    Type: AWS::DynamoDB::Table
+    Properties:
+      TableName: !Sub "${Env}-orders"
+      BillingMode: PAY_PER_REQUEST
+      AttributeDefinitions:
+        - AttributeName: id
+          AttributeType: S
+      KeySchema:
+        - AttributeName: id
+          KeyType: HASH
+      StreamSpecification:
+        StreamViewType: NEW_IMAGE
+      SSESpecification:
+        SSEEnabled: "true"
+      Tags:
+        - Key: env
+          Value: !Ref Env
+        - Key: team
+          Value: orders
+  OrdersQueue:
+    Type: AWS::SQS::Queue
+"#;
+        let tables = TableInfo::from_cloudformation(template).unwrap();
+        assert_eq!(tables.len(), 1);
+        let info = &tables[0];
+        assert_eq!(info.table_name, "OrdersTable");
+        assert!(info.sk.is_none());
+        assert_eq!(info.throughput, None);
+        assert_eq!(info.stream_view_type, Some(StreamViewType::NewImage));
+        assert_eq!(info.encryption, Some(Encryption { kms_key_id: None }));
+        assert_eq!(
+            info.tags,
+            BTreeMap::from([("team".to_string(), "orders".to_string())])
+        );
+    }
+
+    #[test]
+    fn intrinsic_capacity_should_fail_import() {
+        let template = r#"
+Resources:
+  OrdersTable:
+    Type: AWS::DynamoDB::Table
+    Properties:
+      AttributeDefinitions:
+        - AttributeName: id
+          AttributeType: S
+      KeySchema:
+        - AttributeName: id
+          KeyType: HASH
+      ProvisionedThroughput:
+        ReadCapacityUnits: !Ref ReadCapacity
+        WriteCapacityUnits: 5
+"#;
+        let err = TableInfo::from_cloudformation(template).unwrap_err();
+        assert!(matches!(
+            err,
+            DynamoToolsError::TemplateParse(ref msg)
+                if msg.contains("ReadCapacityUnits") && msg.contains(r#"{"Ref":"ReadCapacity"}"#)
+        ));
+
+        let template = template.replace("!Ref ReadCapacity", "5")
+            + "      SSESpecification:\n        SSEEnabled: true\n        KMSMasterKeyId: !Ref OrdersKey\n";
+        let err = TableInfo::from_cloudformation(&template).unwrap_err();
+        assert!(matches!(
+            err,
+            DynamoToolsError::TemplateParse(ref msg)
+                if msg.contains("KMSMasterKeyId") && msg.contains(r#"{"Ref":"OrdersKey"}"#)
+        ));
+    }
+}
@@ -0,0 +1,307 @@
+use crate::error::{DynamoToolsError, Result};
+use crate::{AttrType, TableAttr, TableConfig, TableInfo};
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+// Words that cannot be used as plain identifiers; such names get a trailing `_`
+const RUST_KEYWORDS: &[&str] = &[
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "crate",
+    "do", "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl",
+    "in", "let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref",
+    "return", "self", "static", "struct", "super", "trait", "true", "try", "type", "typeof",
+    "unsafe", "unsized", "use", "virtual", "where", "while", "yield",
+];
+
+impl TableConfig {
+    /// Generates Rust code naming the tables of the config, for use from `build.rs`.
+    ///
+    /// Every table becomes a module (its base name in snake case) with:
+    /// - `TABLE_NAME`, the base name;
+    /// - `attrs::*`, a constant per known attribute name (keys, index keys,
+    ///   projected attributes and the TTL attribute);
+    /// - `indexes::*`, a constant per GSI and LSI name;
+    /// - `Key` and one `<Index>Key` struct per index, with typed fields and a
+    ///   `to_key()` returning the key as a DynamoDB item.
+    ///
+    /// Key fields are `String` for `S`, `i64` for `N` and `Vec<u8>` for `B` attributes.
+    /// The generated code only depends on `aws-sdk-dynamodb`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` ([`DynamoToolsError::TemplateRender`]) if two names map to the
+    /// same Rust identifier.
+    pub fn to_rust(&self) -> Result<String> {
+        let mut out =
+            String::from("// Generated by dynamodb-tools from a table config. Do not edit.\n");
+        let mut modules = BTreeMap::new();
+        for table in &self.tables {
+            let module = snake_case(&table.table_name);
+            if let Some(other) = modules.insert(module.clone(), &table.table_name) {
+                return Err(collision(&module, other, &table.table_name));
+            }
+            out.push('\n');
+            out.push_str(&table_module(&module, table)?);
+        }
+        Ok(out)
+    }
+}
+
+fn table_module(module: &str, table: &TableInfo) -> Result<String> {
+    let mut out = String::new();
+    // Writing to a String cannot fail
+    let w = &mut out;
+    let _ = writeln!(w, "/// Names and keys of table `{}`.", table.table_name);
+    let _ = writeln!(w, "pub mod {} {{", module);
+    let _ = writeln!(w, "    /// The base name of the table.");
+    let _ = writeln!(
+        w,
+        "    pub const TABLE_NAME: &str = {:?};",
+        table.table_name
+    );
+
+    let _ = writeln!(w, "\n    /// Attribute names.");
+    let _ = writeln!(w, "    pub mod attrs {{");
+    let mut consts: BTreeMap<String, String> = BTreeMap::new();
+    for name in attribute_names(table) {
+        let ident = constant_case(&name);
+        match consts.get(&ident) {
+            Some(other) if *other != name => return Err(collision(&ident, other, &name)),
+            Some(_) => continue,
+            None => {
+                let _ = writeln!(w, "        pub const {}: &str = {:?};", ident, name);
+                consts.insert(ident, name);
+            }
+        }
+    }
+    let _ = writeln!(w, "    }}");
+
+    let index_names: Vec<&String> = table
+        .gsis
+        .iter()
+        .map(|gsi| &gsi.name)
+        .chain(table.lsis.iter().map(|lsi| &lsi.name))
+        .collect();
+    if !index_names.is_empty() {
+        let _ = writeln!(w, "\n    /// Index names.");
+        let _ = writeln!(w, "    pub mod indexes {{");
+        let mut seen = BTreeMap::new();
+        for name in index_names {
+            let ident = constant_case(name);
+            if let Some(other) = seen.insert(ident.clone(), name) {
+                return Err(collision(&ident, other, name));
+            }
+            let _ = writeln!(w, "        pub const {}: &str = {:?};", ident, name);
+        }
+        let _ = writeln!(w, "    }}");
+    }
+
+    let mut structs = BTreeMap::new();
+    let mut add_struct = |name: String, doc: String, keys: Vec<&TableAttr>| -> Result<()> {
+        if let Some(other) = structs.insert(name.clone(), doc.clone()) {
+            return Err(collision(&name, &other, &doc));
+        }
+        out.push('\n');
+        out.push_str(&key_struct(&name, &doc, &keys)?);
+        Ok(())
+    };
+    add_struct(
+        "Key".to_string(),
+        "the table".to_string(),
+        [Some(&table.pk), table.sk.as_ref()]
+            .into_iter()
+            .flatten()
+            .collect(),
+    )?;
+    for gsi in &table.gsis {
+        add_struct(
+            format!("{}Key", pascal_case(&gsi.name)),
+            format!("index `{}`", gsi.name),
+            [Some(&gsi.pk), gsi.sk.as_ref()]
+                .into_iter()
+                .flatten()
+                .collect(),
+        )?;
+    }
+    for lsi in &table.lsis {
+        add_struct(
+            format!("{}Key", pascal_case(&lsi.name)),
+            format!("index `{}`", lsi.name),
+            vec![&lsi.pk, &lsi.sk],
+        )?;
+    }
+
+    out.push_str("}\n");
+    Ok(out)
+}
+
+fn key_struct(name: &str, doc: &str, keys: &[&TableAttr]) -> Result<String> {
+    let mut fields: Vec<(String, &TableAttr)> = Vec::new();
+    for key in keys {
+        let field = snake_case(&key.name);
+        if let Some((_, other)) = fields.iter().find(|(f, _)| *f == field) {
+            return Err(collision(&field, &other.name, &key.name));
+        }
+        fields.push((field, *key));
+    }
+
+    let mut out = String::new();
+    let w = &mut out;
+    let _ = writeln!(w, "    /// Key of {}.", doc);
+    let _ = writeln!(w, "    #[derive(Debug, Clone, PartialEq, Eq, Hash)]");
+    let _ = writeln!(w, "    pub struct {} {{", name);
+    for (field, key) in &fields {
+        let ty = match key.attr_type {
+            AttrType::S => "String",
+            AttrType::N => "i64",
+            AttrType::B => "Vec<u8>",
+        };
+        let _ = writeln!(w, "        /// The `{}` attribute.", key.name);
+        let _ = writeln!(w, "        pub {}: {},", field, ty);
+    }
+    let _ = writeln!(w, "    }}");
+
+    let _ = writeln!(w, "\n    impl {} {{", name);
+    let _ = writeln!(w, "        /// Returns the key as a DynamoDB item.");
+    let _ = writeln!(
+        w,
+        "        pub fn to_key(&self) -> ::std::collections::HashMap<String, ::aws_sdk_dynamodb::types::AttributeValue> {{"
+    );
+    let _ = writeln!(w, "            ::std::collections::HashMap::from([");
+    for (field, key) in &fields {
+        let value = match key.attr_type {
+            AttrType::S => format!("S(self.{}.clone())", field),
+            AttrType::N => format!("N(self.{}.to_string())", field),
+            AttrType::B => format!(
+                "B(::aws_sdk_dynamodb::primitives::Blob::new(self.{}.clone()))",
+                field
+            ),
+        };
+        let _ = writeln!(
+            w,
+            "                (attrs::{}.to_string(), ::aws_sdk_dynamodb::types::AttributeValue::{}),",
+            constant_case(&key.name),
+            value
+        );
+    }
+    let _ = writeln!(w, "            ])");
+    let _ = writeln!(w, "        }}");
+    let _ = writeln!(w, "    }}");
+    Ok(out)
+}
+
+// Every attribute name the config mentions for `table`, keys first
+fn attribute_names(table: &TableInfo) -> Vec<String> {
+    let mut names: Vec<String> = table.key_names();
+    let gsi_names = table.gsis.iter().flat_map(|gsi| {
+        [Some(&gsi.pk.name), gsi.sk.as_ref().map(|sk| &sk.name)]
+            .into_iter()
+            .flatten()
+            .chain(&gsi.attrs)
+    });
+    let lsi_names = table
+        .lsis
+        .iter()
+        .flat_map(|lsi| [&lsi.pk.name, &lsi.sk.name].into_iter().chain(&lsi.attrs));
+    let others = table
+        .attrs
+        .iter()
+        .map(|attr| &attr.name)
+        .chain(gsi_names)
+        .chain(lsi_names)
+        .chain(&table.ttl_attribute);
+    for name in others {
+        if !names.contains(name) {
+            names.push(name.clone());
+        }
+    }
+    names
+}
+
+fn collision(ident: &str, a: &str, b: &str) -> DynamoToolsError {
+    DynamoToolsError::TemplateRender(format!(
+        "'{}' and '{}' both map to the Rust name '{}'",
+        a, b, ident
+    ))
+}
+
+// Splits a name into lowercase words at separators and lower-to-upper case changes
+fn words(name: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    let mut prev_lower = false;
+    for c in name.chars() {
+        if !c.is_ascii_alphanumeric() {
+            if !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+            prev_lower = false;
+            continue;
+        }
+        if c.is_ascii_uppercase() && prev_lower {
+            words.push(std::mem::take(&mut current));
+        }
+        prev_lower = c.is_ascii_lowercase() || (c.is_ascii_digit() && prev_lower);
+        current.push(c.to_ascii_lowercase());
+    }
+    if !current.is_empty() {
+        words.push(current);
+    }
+    words
+}
+
+// Makes `name` a valid identifier: no leading digit, no keyword
+fn identifier(mut name: String) -> String {
+    if name.is_empty() || name.starts_with(|c: char| c.is_ascii_digit()) {
+        name.insert(0, '_');
+    }
+    if RUST_KEYWORDS.contains(&name.as_str()) {
+        name.push('_');
+    }
+    name
+}
+
+fn snake_case(name: &str) -> String {
+    identifier(words(name).join("_"))
+}
+
+fn constant_case(name: &str) -> String {
+    identifier(words(name).join("_").to_ascii_uppercase())
+}
+
+fn pascal_case(name: &str) -> String {
+    let pascal = words(name)
+        .iter()
+        .map(|word| word[..1].to_ascii_uppercase() + &word[1..])
+        .collect();
+    identifier(pascal)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_should_become_identifiers() {
+        assert_eq!(snake_case("orderStatus"), "order_status");
+        assert_eq!(snake_case("user-events.v2"), "user_events_v2");
+        assert_eq!(snake_case("type"), "type_");
+        assert_eq!(constant_case("gsi1pk"), "GSI1PK");
+        assert_eq!(constant_case("orderStatus"), "ORDER_STATUS");
+        assert_eq!(constant_case("2fa"), "_2FA");
+        assert_eq!(pascal_case("by_status"), "ByStatus");
+        assert_eq!(pascal_case("gsi1"), "Gsi1");
+    }
+
+    #[test]
+    fn colliding_names_should_be_rejected() {
+        let mut config = TableConfig::load_from_file("fixtures/dev.yml").unwrap();
+        config.tables[0].attrs.push(TableAttr {
+            name: "GSI1PK".to_string(),
+            attr_type: AttrType::S,
+        });
+        let err = config.to_rust().unwrap_err();
+        assert!(
+            matches!(err, DynamoToolsError::TemplateRender(ref msg) if msg.contains("'GSI1PK'"))
+        );
+    }
+}
@@ -3,12 +3,15 @@ use aws_sdk_dynamodb::{
     operation::create_table::CreateTableInput,
     types::{
         AttributeDefinition, BillingMode, GlobalSecondaryIndex, KeySchemaElement, KeyType,
-        LocalSecondaryIndex, Projection, ProjectionType, ProvisionedThroughput,
-        ScalarAttributeType,
+        LocalSecondaryIndex, OnDemandThroughput, OnDemandThroughputOverride, Projection,
+        ProjectionType, ProvisionedThroughput, ProvisionedThroughputOverride,
+        ReplicaGlobalSecondaryIndex, ScalarAttributeType, SseSpecification, SseType,
+        StreamSpecification, StreamViewType as SdkStreamViewType, TableClass as SdkTableClass, Tag,
+        WarmThroughput as SdkWarmThroughput,
     },
 };
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 use std::{fs::File, io::BufReader, path::Path};
 
 /// Represents the main configuration loaded from a YAML file.
@@ -31,6 +34,15 @@ pub struct TableConfig {
     /// A list of table schemas to be managed by the connector.
     #[serde(default)]
     pub tables: Vec<TableInfo>,
+    /// If set, the connector deletes orphaned tables of the configured base names
+    /// that are older than this many seconds before creating its own tables
+    /// (see [`crate::sweep_orphans`]).
+    #[serde(default)]
+    pub sweep_orphans_older_than: Option<u64>,
+    /// Tags applied to every created table. Tags of the same key in
+    /// [`TableInfo::tags`] take precedence.
+    #[serde(default)]
+    pub tags: BTreeMap<String, String>,
 }
 
 /// Defines the detailed schema for a single DynamoDB table.
@@ -57,21 +69,189 @@ pub struct TableInfo {
     /// Local Secondary Index definitions.
     #[serde(default)]
     pub lsis: Vec<TableLsi>,
-    /// Optional provisioned throughput settings. If `None`, uses Pay-Per-Request billing.
+    /// Optional throughput settings. If `None` or [`Throughput::OnDemand`],
+    /// uses Pay-Per-Request billing.
     #[serde(default)]
     pub throughput: Option<Throughput>,
-    /// Optional path to a JSON file containing an array of items to seed into the table after creation.
+    /// Optional warm throughput settings for the table.
+    #[serde(default)]
+    pub warm_throughput: Option<WarmThroughput>,
+    /// Optional auto scaling for a provisioned table (requires the `auto_scaling`
+    /// feature to be applied).
+    #[serde(default)]
+    pub auto_scaling: Option<AutoScaling>,
+    /// Optional path to a file of items to seed into the table after creation.
+    /// Either a JSON array or any other [`crate::DataFormat`] (detected from the content).
     #[serde(default)]
     pub seed_data_file: Option<String>,
+    /// How seed items are written into the table. Defaults to [`SeedMode::Overwrite`].
+    #[serde(default)]
+    pub seed_mode: SeedMode,
+    /// Tags to apply to the table, merged over [`TableConfig::tags`].
+    #[serde(default)]
+    pub tags: BTreeMap<String, String>,
+    /// Optional server-side encryption with a KMS key. If `None`, DynamoDB's
+    /// default encryption with an AWS owned key is used.
+    #[serde(default)]
+    pub encryption: Option<Encryption>,
+    /// Optional table class. If `None`, DynamoDB defaults to `STANDARD`.
+    #[serde(default)]
+    pub table_class: Option<TableClass>,
+    /// Whether the table is protected against deletion.
+    #[serde(default)]
+    pub deletion_protection: bool,
+    /// Whether point-in-time recovery (continuous backups) is enabled.
+    /// Applied with `UpdateContinuousBackups` once the table is active.
+    #[serde(default)]
+    pub point_in_time_recovery: bool,
+    /// Replicas in other regions, turning the table into a global table.
+    /// Added with `UpdateTable` one at a time once the table is active. DynamoDB
+    /// Local has no global tables, so replicas are only removed by
+    /// `DynamodbConnector::delete_tables` or by `sweep_orphans`, not on drop.
+    #[serde(default)]
+    pub replicas: Vec<Replica>,
+    /// Optional name of the attribute holding the expiry time (epoch seconds) of
+    /// items. Time to live is enabled with `UpdateTimeToLive` once the table is active.
+    #[serde(default)]
+    pub ttl_attribute: Option<String>,
+    /// Optional DynamoDB Streams view type. Tables with replicas default to
+    /// [`StreamViewType::NewAndOldImages`].
+    #[serde(default)]
+    pub stream_view_type: Option<StreamViewType>,
 }
 
-/// Defines provisioned throughput settings (read/write capacity units).
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct Throughput {
-    /// Read Capacity Units (RCU).
-    pub read: i64,
-    /// Write Capacity Units (WCU).
-    pub write: i64,
+/// What a DynamoDB stream records for each modified item.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum StreamViewType {
+    /// Only the key attributes.
+    KeysOnly,
+    /// The item as it appears after the modification.
+    NewImage,
+    /// The item as it appeared before the modification.
+    OldImage,
+    /// Both the new and the old item images.
+    NewAndOldImages,
+}
+
+/// A replica of a global table in another region.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Replica {
+    /// The region of the replica, e.g. `eu-west-1`.
+    pub region: String,
+    /// Per-GSI throughput overrides in this replica.
+    #[serde(default)]
+    pub gsis: Vec<ReplicaGsi>,
+    /// Optional table class of the replica. If `None`, the class of the table is used.
+    #[serde(default)]
+    pub table_class: Option<TableClass>,
+}
+
+/// Read throughput overrides for a GSI in a replica.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ReplicaGsi {
+    /// The name of the GSI.
+    pub name: String,
+    /// Read capacity units, for provisioned tables.
+    #[serde(default)]
+    pub read: Option<i64>,
+    /// Maximum read request units per second, for on-demand tables.
+    #[serde(default)]
+    pub max_read: Option<i64>,
+}
+
+/// Server-side encryption settings using AWS KMS.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Encryption {
+    /// The KMS key ID, ARN or alias. If `None`, the AWS managed key
+    /// (`alias/aws/dynamodb`) is used. The Terraform export requires an ARN.
+    #[serde(default)]
+    pub kms_key_id: Option<String>,
+}
+
+/// The storage class of a table.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum TableClass {
+    /// The default class, optimized for frequently accessed data.
+    Standard,
+    /// Lower storage cost for infrequently accessed data.
+    StandardInfrequentAccess,
+}
+
+/// Controls how seed data is written into a table.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SeedMode {
+    /// Plain `BatchWriteItem` puts. Items with an existing key are replaced.
+    #[default]
+    Overwrite,
+    /// Conditional puts on the primary key. Items whose key already exists are skipped.
+    InsertIfAbsent,
+    /// Chunks of `TransactWriteItems` of conditional puts, which fail if any item
+    /// key already exists. If any chunk fails, the items created by earlier chunks
+    /// are deleted again so the table is left as it was.
+    AllOrNothing,
+}
+
+/// Defines the throughput settings of a table or GSI.
+///
+/// In YAML, `{ read, write }` selects provisioned capacity and
+/// `{ max_read, max_write }` selects on-demand capacity with request limits.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(untagged, deny_unknown_fields)]
+pub enum Throughput {
+    /// Provisioned capacity.
+    Provisioned {
+        /// Read Capacity Units (RCU).
+        read: i64,
+        /// Write Capacity Units (WCU).
+        write: i64,
+    },
+    /// On-demand (pay-per-request) capacity, optionally capped.
+    OnDemand {
+        /// Maximum read request units per second. Unlimited if `None`.
+        #[serde(default)]
+        max_read: Option<i64>,
+        /// Maximum write request units per second. Unlimited if `None`.
+        #[serde(default)]
+        max_write: Option<i64>,
+    },
+}
+
+/// Defines warm throughput: the reads and writes per second a table or GSI
+/// can instantly sustain.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct WarmThroughput {
+    /// Read units per second.
+    #[serde(default)]
+    pub read_units_per_second: Option<i64>,
+    /// Write units per second.
+    #[serde(default)]
+    pub write_units_per_second: Option<i64>,
+}
+
+/// Application Auto Scaling settings for the capacity of a provisioned table or GSI.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct AutoScaling {
+    /// Scaling of read capacity units.
+    #[serde(default)]
+    pub read: Option<ScalingSettings>,
+    /// Scaling of write capacity units.
+    #[serde(default)]
+    pub write: Option<ScalingSettings>,
+}
+
+/// Target tracking settings for one capacity dimension.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ScalingSettings {
+    /// Minimum capacity units.
+    pub min: i32,
+    /// Maximum capacity units.
+    pub max: i32,
+    /// Target utilization in percent. Defaults to 70.
+    #[serde(default = "default_target_utilization")]
+    pub target_utilization: f64,
 }
 
 /// Defines a single DynamoDB attribute (name and type).
@@ -95,6 +275,31 @@ pub enum AttrType {
     B,
 }
 
+/// A type whose values are stored as the items of a table.
+///
+/// With the `derive` feature, `#[derive(DynamoTable)]` implements it from the key
+/// annotations on the struct fields, so the schema lives next to the item type:
+///
+/// ```rust,ignore
+/// #[derive(Serialize, Deserialize, DynamoTable)]
+/// #[dynamo(table = "orders")]
+/// struct Order {
+///     #[dynamo(pk)]
+///     user_id: String,
+///     #[dynamo(sk)]
+///     #[dynamo(gsi = "by_status", sk)]
+///     created_at: i64,
+///     #[dynamo(gsi = "by_status", pk)]
+///     status: String,
+/// }
+///
+/// let config = TableConfig::new(region, endpoint, true, vec![Order::table_info()]);
+/// ```
+pub trait DynamoTable {
+    /// Returns the schema of the table.
+    fn table_info() -> TableInfo;
+}
+
 /// Defines a Global Secondary Index (GSI).
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TableGsi {
@@ -108,9 +313,19 @@ pub struct TableGsi {
     /// Attributes to project into the GSI (only used if projection type is INCLUDE).
     #[serde(default)]
     pub attrs: Vec<String>,
-    /// Optional provisioned throughput for the GSI.
+    /// Optional projection type. If `None`, `INCLUDE` is used with `attrs`.
+    #[serde(default)]
+    pub projection: Option<IndexProjection>,
+    /// Optional throughput for the GSI. Must match the billing mode of the table.
     #[serde(default)]
     pub throughput: Option<Throughput>,
+    /// Optional warm throughput settings for the GSI.
+    #[serde(default)]
+    pub warm_throughput: Option<WarmThroughput>,
+    /// Optional auto scaling for a provisioned GSI (requires the `auto_scaling`
+    /// feature to be applied).
+    #[serde(default)]
+    pub auto_scaling: Option<AutoScaling>,
 }
 
 /// Defines a Local Secondary Index (LSI).
@@ -125,6 +340,22 @@ pub struct TableLsi {
     /// Attributes to project into the LSI (only used if projection type is INCLUDE).
     #[serde(default)]
     pub attrs: Vec<String>,
+    /// Optional projection type. If `None`, `ALL` is used when `attrs` is empty
+    /// and `INCLUDE` otherwise.
+    #[serde(default)]
+    pub projection: Option<IndexProjection>,
+}
+
+/// The attributes copied into a secondary index.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum IndexProjection {
+    /// All attributes of the table.
+    All,
+    /// Only the table and index keys.
+    KeysOnly,
+    /// The keys plus the attributes listed in `attrs`.
+    Include,
 }
 
 // Internal helper function for default region
@@ -132,6 +363,11 @@ fn default_aws_region() -> String {
     "us-east-1".to_string()
 }
 
+// Internal helper function for default auto scaling target utilization
+fn default_target_utilization() -> f64 {
+    70.0
+}
+
 impl From<AttrType> for ScalarAttributeType {
     fn from(attr_type: AttrType) -> Self {
         match attr_type {
@@ -142,6 +378,109 @@ impl From<AttrType> for ScalarAttributeType {
     }
 }
 
+impl AttrType {
+    /// Parses a DynamoDB attribute type name (`S`, `N` or `B`).
+    pub(crate) fn parse(name: &str) -> Result<Self> {
+        match name {
+            "S" => Ok(AttrType::S),
+            "N" => Ok(AttrType::N),
+            "B" => Ok(AttrType::B),
+            other => Err(DynamoToolsError::TemplateParse(format!(
+                "unsupported attribute type '{}'",
+                other
+            ))),
+        }
+    }
+}
+
+/// Looks up the attribute `name` in the attribute definitions of an imported table.
+pub(crate) fn defined_attr(defs: &BTreeMap<String, AttrType>, name: &str) -> Result<TableAttr> {
+    let attr_type = defs.get(name).ok_or_else(|| {
+        DynamoToolsError::TemplateParse(format!("key attribute '{}' is not defined", name))
+    })?;
+    Ok(TableAttr {
+        name: name.to_string(),
+        attr_type: attr_type.clone(),
+    })
+}
+
+impl From<TableClass> for SdkTableClass {
+    fn from(class: TableClass) -> Self {
+        match class {
+            TableClass::Standard => SdkTableClass::Standard,
+            TableClass::StandardInfrequentAccess => SdkTableClass::StandardInfrequentAccess,
+        }
+    }
+}
+
+impl From<StreamViewType> for SdkStreamViewType {
+    fn from(view_type: StreamViewType) -> Self {
+        match view_type {
+            StreamViewType::KeysOnly => SdkStreamViewType::KeysOnly,
+            StreamViewType::NewImage => SdkStreamViewType::NewImage,
+            StreamViewType::OldImage => SdkStreamViewType::OldImage,
+            StreamViewType::NewAndOldImages => SdkStreamViewType::NewAndOldImages,
+        }
+    }
+}
+
+impl From<Encryption> for SseSpecification {
+    fn from(encryption: Encryption) -> Self {
+        SseSpecification::builder()
+            .enabled(true)
+            .sse_type(SseType::Kms)
+            .set_kms_master_key_id(encryption.kms_key_id)
+            .build()
+    }
+}
+
+impl From<WarmThroughput> for SdkWarmThroughput {
+    fn from(warm: WarmThroughput) -> Self {
+        SdkWarmThroughput::builder()
+            .set_read_units_per_second(warm.read_units_per_second)
+            .set_write_units_per_second(warm.write_units_per_second)
+            .build()
+    }
+}
+
+impl From<ReplicaGsi> for ReplicaGlobalSecondaryIndex {
+    fn from(gsi: ReplicaGsi) -> Self {
+        ReplicaGlobalSecondaryIndex::builder()
+            .index_name(gsi.name)
+            .set_provisioned_throughput_override(gsi.read.map(|read| {
+                ProvisionedThroughputOverride::builder()
+                    .read_capacity_units(read)
+                    .build()
+            }))
+            .set_on_demand_throughput_override(gsi.max_read.map(|max_read| {
+                OnDemandThroughputOverride::builder()
+                    .max_read_request_units(max_read)
+                    .build()
+            }))
+            .build()
+            .unwrap()
+    }
+}
+
+fn on_demand_throughput(max_read: Option<i64>, max_write: Option<i64>) -> OnDemandThroughput {
+    OnDemandThroughput::builder()
+        .set_max_read_request_units(max_read)
+        .set_max_write_request_units(max_write)
+        .build()
+}
+
+fn projection(projection_type: IndexProjection, attrs: Vec<String>) -> Projection {
+    let builder = Projection::builder();
+    match projection_type {
+        IndexProjection::All => builder.projection_type(ProjectionType::All),
+        IndexProjection::KeysOnly => builder.projection_type(ProjectionType::KeysOnly),
+        IndexProjection::Include => builder
+            .projection_type(ProjectionType::Include)
+            .set_non_key_attributes(Some(attrs)),
+    }
+    .build()
+}
+
 impl From<TableAttr> for AttributeDefinition {
     fn from(attr: TableAttr) -> Self {
         let attr_type = attr.attr_type.into();
@@ -171,6 +510,36 @@ impl TableAttr {
     }
 }
 
+impl TableGsi {
+    /// Creates a `TableGsi` with the given key schema and every other setting
+    /// left at its default.
+    pub fn new(name: impl Into<String>, pk: TableAttr, sk: Option<TableAttr>) -> Self {
+        Self {
+            name: name.into(),
+            pk,
+            sk,
+            attrs: Vec::new(),
+            projection: None,
+            throughput: None,
+            warm_throughput: None,
+            auto_scaling: None,
+        }
+    }
+}
+
+impl TableLsi {
+    /// Creates a `TableLsi` with the given key schema and the default projection.
+    pub fn new(name: impl Into<String>, pk: TableAttr, sk: TableAttr) -> Self {
+        Self {
+            name: name.into(),
+            pk,
+            sk,
+            attrs: Vec::new(),
+            projection: None,
+        }
+    }
+}
+
 impl From<TableGsi> for GlobalSecondaryIndex {
     fn from(gsi: TableGsi) -> Self {
         let pk = gsi.pk.to_pk();
@@ -184,21 +553,31 @@ impl From<TableGsi> for GlobalSecondaryIndex {
 
         let mut builder = GlobalSecondaryIndex::builder()
             .set_key_schema(Some(key_schema))
-            .projection(
-                Projection::builder()
-                    .projection_type(ProjectionType::Include)
-                    .set_non_key_attributes(Some(gsi.attrs))
-                    .build(),
-            )
+            .projection(projection(
+                gsi.projection.unwrap_or(IndexProjection::Include),
+                gsi.attrs,
+            ))
             .index_name(gsi.name);
 
-        if let Some(throughput) = gsi.throughput {
-            let pt = ProvisionedThroughput::builder()
-                .read_capacity_units(throughput.read)
-                .write_capacity_units(throughput.write)
-                .build()
-                .unwrap();
-            builder = builder.provisioned_throughput(pt);
+        match gsi.throughput {
+            Some(Throughput::Provisioned { read, write }) => {
+                let pt = ProvisionedThroughput::builder()
+                    .read_capacity_units(read)
+                    .write_capacity_units(write)
+                    .build()
+                    .unwrap();
+                builder = builder.provisioned_throughput(pt);
+            }
+            Some(Throughput::OnDemand {
+                max_read,
+                max_write,
+            }) => {
+                builder = builder.on_demand_throughput(on_demand_throughput(max_read, max_write));
+            }
+            None => {}
+        }
+        if let Some(warm) = gsi.warm_throughput {
+            builder = builder.warm_throughput(warm.into());
         }
         builder.build().unwrap()
     }
@@ -209,19 +588,14 @@ impl From<TableLsi> for LocalSecondaryIndex {
         let pk = lsi.pk.to_pk();
         let sk = lsi.sk.to_sk();
         let key_schema = vec![pk, sk];
-        let projection = if lsi.attrs.is_empty() {
-            Projection::builder()
-                .projection_type(ProjectionType::All)
-                .build()
+        let projection_type = lsi.projection.unwrap_or(if lsi.attrs.is_empty() {
+            IndexProjection::All
         } else {
-            Projection::builder()
-                .projection_type(ProjectionType::Include)
-                .set_non_key_attributes(Some(lsi.attrs))
-                .build()
-        };
+            IndexProjection::Include
+        });
         LocalSecondaryIndex::builder()
             .set_key_schema(Some(key_schema))
-            .projection(projection)
+            .projection(projection(projection_type, lsi.attrs))
             .index_name(lsi.name)
             .build()
             .unwrap()
@@ -232,8 +606,10 @@ impl TryFrom<TableInfo> for CreateTableInput {
     type Error = DynamoToolsError;
 
     fn try_from(config: TableInfo) -> Result<Self> {
-        // Use a HashMap to collect unique attribute definitions by name
-        let mut attribute_map: HashMap<String, TableAttr> = HashMap::new();
+        let stream_view_type = config.effective_stream_view_type();
+
+        // Collect unique attribute definitions by name, sorted for stable output
+        let mut attribute_map: BTreeMap<String, TableAttr> = BTreeMap::new();
 
         // 1. Add base table keys
         attribute_map.insert(config.pk.name.clone(), config.pk.clone());
@@ -295,12 +671,32 @@ impl TryFrom<TableInfo> for CreateTableInput {
         if !lsis.is_empty() {
             builder = builder.set_local_secondary_indexes(Some(lsis));
         }
+        if !config.tags.is_empty() {
+            builder = builder.set_tags(Some(to_tags(&config.tags)?));
+        }
+        if let Some(encryption) = config.encryption {
+            builder = builder.sse_specification(encryption.into());
+        }
+        if let Some(table_class) = config.table_class {
+            builder = builder.table_class(table_class.into());
+        }
+        if config.deletion_protection {
+            builder = builder.deletion_protection_enabled(true);
+        }
+        if let Some(view_type) = stream_view_type {
+            builder = builder.stream_specification(
+                StreamSpecification::builder()
+                    .stream_enabled(true)
+                    .stream_view_type(view_type.into())
+                    .build()?,
+            );
+        }
 
         match config.throughput {
-            Some(throughput) => {
+            Some(Throughput::Provisioned { read, write }) => {
                 let pt = ProvisionedThroughput::builder()
-                    .read_capacity_units(throughput.read)
-                    .write_capacity_units(throughput.write)
+                    .read_capacity_units(read)
+                    .write_capacity_units(write)
                     .build()
                     .map_err(|e| {
                         DynamoToolsError::Internal(format!(
@@ -310,16 +706,40 @@ impl TryFrom<TableInfo> for CreateTableInput {
                     })?;
                 builder = builder.provisioned_throughput(pt);
             }
+            Some(Throughput::OnDemand {
+                max_read,
+                max_write,
+            }) => {
+                builder = builder
+                    .billing_mode(BillingMode::PayPerRequest)
+                    .on_demand_throughput(on_demand_throughput(max_read, max_write));
+            }
             None => {
                 builder = builder.billing_mode(BillingMode::PayPerRequest);
             }
         }
+        if let Some(warm) = config.warm_throughput {
+            builder = builder.warm_throughput(warm.into());
+        }
         // --- End Build ---
 
         builder.build().map_err(DynamoToolsError::AwsSdkConfig)
     }
 }
 
+/// Converts a tag map into SDK `Tag`s.
+pub(crate) fn to_tags(tags: &BTreeMap<String, String>) -> Result<Vec<Tag>> {
+    tags.iter()
+        .map(|(key, value)| {
+            Tag::builder()
+                .key(key)
+                .value(value)
+                .build()
+                .map_err(DynamoToolsError::AwsSdkConfig)
+        })
+        .collect()
+}
+
 impl TableConfig {
     /// Loads [`TableConfig`] from a YAML file.
     ///
@@ -358,11 +778,64 @@ impl TableConfig {
             endpoint,
             delete_on_exit,
             tables,
+            sweep_orphans_older_than: None,
+            tags: BTreeMap::new(),
         }
     }
+
+    /// Returns the tags configured for `table`: the global [`TableConfig::tags`]
+    /// merged with the table's own [`TableInfo::tags`], which take precedence.
+    pub fn table_tags(&self, table: &TableInfo) -> BTreeMap<String, String> {
+        let mut tags = self.tags.clone();
+        tags.extend(table.tags.clone());
+        tags
+    }
 }
 
 impl TableInfo {
+    /// Creates a `TableInfo` with the given key schema and every other setting
+    /// left at its default.
+    pub fn new(table_name: impl Into<String>, pk: TableAttr, sk: Option<TableAttr>) -> Self {
+        Self {
+            table_name: table_name.into(),
+            pk,
+            sk,
+            attrs: Vec::new(),
+            gsis: Vec::new(),
+            lsis: Vec::new(),
+            throughput: None,
+            warm_throughput: None,
+            auto_scaling: None,
+            seed_data_file: None,
+            seed_mode: SeedMode::default(),
+            tags: BTreeMap::new(),
+            encryption: None,
+            table_class: None,
+            deletion_protection: false,
+            point_in_time_recovery: false,
+            replicas: Vec::new(),
+            ttl_attribute: None,
+            stream_view_type: None,
+        }
+    }
+
+    /// Returns the stream view type of the table: the configured one, or
+    /// [`StreamViewType::NewAndOldImages`] for tables with replicas, which
+    /// replication requires.
+    pub fn effective_stream_view_type(&self) -> Option<StreamViewType> {
+        self.stream_view_type
+            .or_else(|| (!self.replicas.is_empty()).then_some(StreamViewType::NewAndOldImages))
+    }
+
+    /// Returns the names of the primary key attributes (partition key first).
+    pub fn key_names(&self) -> Vec<String> {
+        let mut names = vec![self.pk.name.clone()];
+        if let Some(ref sk) = self.sk {
+            names.push(sk.name.clone());
+        }
+        names
+    }
+
     /// Loads [`TableInfo`] directly from a YAML file.
     ///
     /// Generally, it's preferred to load the full [`TableConfig`].
@@ -441,5 +914,168 @@ mod tests {
         assert_eq!(info.table_name, "users");
         assert_eq!(info.pk.name, "pk");
         assert_eq!(info.pk.attr_type, AttrType::S);
+        assert_eq!(info.seed_mode, SeedMode::Overwrite);
+        assert_eq!(info.key_names(), vec!["pk".to_string(), "sk".to_string()]);
+    }
+
+    #[test]
+    fn table_tags_should_override_global_tags() {
+        let mut config = TableConfig::load_from_file("fixtures/dev.yml").unwrap();
+        config.tags = BTreeMap::from([
+            ("team".to_string(), "platform".to_string()),
+            ("env".to_string(), "test".to_string()),
+        ]);
+        config.tables[0].tags = BTreeMap::from([("env".to_string(), "ci".to_string())]);
+
+        let tags = config.table_tags(&config.tables[0]);
+        assert_eq!(tags.get("team"), Some(&"platform".to_string()));
+        assert_eq!(tags.get("env"), Some(&"ci".to_string()));
+
+        let input = CreateTableInput::try_from(config.tables[0].clone()).unwrap();
+        assert_eq!(input.tags().len(), 1);
+    }
+
+    #[test]
+    fn table_settings_could_be_converted() {
+        let info = TableInfo::load(
+            r#"
+table_name: orders
+pk:
+  name: id
+  type: S
+encryption:
+  kms_key_id: alias/orders
+table_class: STANDARD_INFREQUENT_ACCESS
+deletion_protection: true
+point_in_time_recovery: true
+"#,
+        )
+        .unwrap();
+        assert!(info.point_in_time_recovery);
+
+        let input = CreateTableInput::try_from(info).unwrap();
+        let sse = input.sse_specification().unwrap();
+        assert_eq!(sse.sse_type(), Some(&SseType::Kms));
+        assert_eq!(sse.kms_master_key_id(), Some("alias/orders"));
+        assert_eq!(
+            input.table_class(),
+            Some(&SdkTableClass::StandardInfrequentAccess)
+        );
+        assert_eq!(input.deletion_protection_enabled(), Some(true));
+    }
+
+    #[test]
+    fn replicas_could_be_parsed_and_converted() {
+        let info = TableInfo::load(
+            r#"
+table_name: orders
+pk:
+  name: id
+  type: S
+replicas:
+  - region: eu-west-1
+    table_class: STANDARD_INFREQUENT_ACCESS
+    gsis:
+      - name: by_customer
+        max_read: 10
+"#,
+        )
+        .unwrap();
+        let replica = &info.replicas[0];
+        assert_eq!(replica.region, "eu-west-1");
+        assert_eq!(
+            replica.table_class,
+            Some(TableClass::StandardInfrequentAccess)
+        );
+
+        let gsi = ReplicaGlobalSecondaryIndex::from(replica.gsis[0].clone());
+        assert_eq!(gsi.index_name(), "by_customer");
+        assert!(gsi.provisioned_throughput_override().is_none());
+        assert_eq!(
+            gsi.on_demand_throughput_override()
+                .and_then(|o| o.max_read_request_units()),
+            Some(10)
+        );
+
+        let input = CreateTableInput::try_from(info).unwrap();
+        let stream = input.stream_specification().unwrap();
+        assert!(stream.stream_enabled());
+        assert_eq!(
+            stream.stream_view_type(),
+            Some(&SdkStreamViewType::NewAndOldImages)
+        );
+    }
+
+    #[test]
+    fn throughput_could_be_parsed_and_converted() {
+        let info = TableInfo::load(
+            r#"
+table_name: orders
+pk:
+  name: id
+  type: S
+throughput:
+  max_read: 100
+warm_throughput:
+  read_units_per_second: 15000
+gsis:
+  - name: by_customer
+    pk:
+      name: customer_id
+      type: S
+    throughput:
+      max_read: 10
+      max_write: 5
+"#,
+        )
+        .unwrap();
+        assert_eq!(
+            info.throughput,
+            Some(Throughput::OnDemand {
+                max_read: Some(100),
+                max_write: None
+            })
+        );
+
+        let input = CreateTableInput::try_from(info).unwrap();
+        assert_eq!(input.billing_mode(), Some(&BillingMode::PayPerRequest));
+        assert_eq!(
+            input
+                .on_demand_throughput()
+                .unwrap()
+                .max_read_request_units(),
+            Some(100)
+        );
+        assert_eq!(
+            input.warm_throughput().unwrap().read_units_per_second(),
+            Some(15000)
+        );
+        let gsi = &input.global_secondary_indexes()[0];
+        assert_eq!(
+            gsi.on_demand_throughput()
+                .unwrap()
+                .max_write_request_units(),
+            Some(5)
+        );
+
+        let provisioned: Throughput = serde_yml::from_str("{ read: 5, write: 10 }").unwrap();
+        assert_eq!(provisioned, Throughput::Provisioned { read: 5, write: 10 });
+        assert!(serde_yml::from_str::<Throughput>("{ read: 5 }").is_err());
+    }
+
+    #[test]
+    fn seed_mode_could_be_parsed() {
+        let info = TableInfo::load(
+            r#"
+table_name: users
+pk:
+  name: id
+  type: S
+seed_mode: insert_if_absent
+"#,
+        )
+        .unwrap();
+        assert_eq!(info.seed_mode, SeedMode::InsertIfAbsent);
+        assert_eq!(info.key_names(), vec!["id".to_string()]);
     }
 }
@@ -1,15 +1,32 @@
-use crate::TableConfig;
+use crate::Replica;
 use crate::error::{DynamoToolsError, Result};
+use crate::item::{self, DataFormat};
+use crate::sweep::{self, SweepTarget};
+use crate::{
+    IndexReport, Snapshot, TableConfig, TableHandle, TableInfo, WorkbenchModel, config,
+    index_check, scan, seed,
+};
 use aws_config::meta::region::RegionProviderChain;
 use aws_config::{BehaviorVersion, Region};
+use aws_sdk_dynamodb::client::Waiters;
 use aws_sdk_dynamodb::config::Credentials;
-use aws_sdk_dynamodb::types::{AttributeValue, PutRequest, WriteRequest};
+use aws_sdk_dynamodb::primitives::{DateTime, DateTimeFormat};
+use aws_sdk_dynamodb::types::{
+    CreateReplicationGroupMemberAction, PointInTimeRecoverySpecification, ReplicationGroupUpdate,
+    TimeToLiveSpecification,
+};
 use aws_sdk_dynamodb::{Client, operation::create_table::CreateTableInput};
-use serde_json::Value;
-use std::{collections::HashMap, fs, path::Path};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::OnceLock;
+use std::time::{Duration, SystemTime};
+use std::{fs, path::Path};
 #[cfg(feature = "test_utils")]
 use tokio::runtime::Runtime;
 
+// Maximum time to wait for a table to become active before configuring it further
+const TABLE_WAIT_TIMEOUT: Duration = Duration::from_secs(300);
+
 /// Provides a connection to DynamoDB, potentially managing test table lifecycles.
 ///
 /// This struct encapsulates an AWS DynamoDB client (`aws_sdk_dynamodb::Client`).
@@ -18,15 +35,18 @@ use tokio::runtime::Runtime;
 ///
 /// If the `test_utils` feature is enabled and `delete_on_exit` is true in the
 /// configuration, the created tables will be automatically deleted when this
-/// connector is dropped.
+/// connector is dropped, if it uses a local endpoint. On AWS, call
+/// [`DynamodbConnector::delete_tables`] instead.
 #[derive(Debug)]
 pub struct DynamodbConnector {
     client: Option<Client>,
     // Map base table name to actual unique table name created
     created_tables: HashMap<String, String>,
-    // Keep track of the original config for Drop
-    #[cfg(feature = "test_utils")]
+    // Keep track of the original config for seeding and Drop
     config: TableConfig,
+    // Set once auto scaling targets are registered, to deregister them in `delete_tables`
+    #[cfg(feature = "auto_scaling")]
+    auto_scaling: Option<aws_sdk_applicationautoscaling::Client>,
 }
 
 impl DynamodbConnector {
@@ -43,6 +63,35 @@ impl DynamodbConnector {
         DynamodbConnector::try_new(config).await
     }
 
+    /// Creates a new connector with the tables of a NoSQL Workbench data model added
+    /// to `config`, each seeded with the sample data of the model.
+    ///
+    /// The region, endpoint and other settings come from `config`; see
+    /// [`DynamodbConnector::try_new`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if creating the connector fails or if writing the sample data fails.
+    pub async fn try_from_workbench(
+        mut config: TableConfig,
+        model: &WorkbenchModel,
+    ) -> Result<Self> {
+        config.tables.extend(model.table_infos());
+        let connector = DynamodbConnector::try_new(config).await?;
+        for table in model.tables.iter().filter(|t| !t.items.is_empty()) {
+            println!(
+                "[INFO] Seeding {} items from model '{}' into table '{}'",
+                table.items.len(),
+                model.name,
+                table.info.table_name
+            );
+            connector
+                .write_seed_items(&table.info.table_name, table.items.clone())
+                .await?;
+        }
+        Ok(connector)
+    }
+
     /// Returns a reference to the underlying `aws_sdk_dynamodb::Client`.
     ///
     /// # Errors
@@ -63,6 +112,20 @@ impl DynamodbConnector {
         self.created_tables.get(base_name).map(|s| s.as_str())
     }
 
+    /// Returns a handle on a table created by this connector, given its base name.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` ([`DynamoToolsError::UnknownTable`]) if `base_name` is not a table
+    /// created by this connector.
+    pub fn table(&self, base_name: &str) -> Result<TableHandle> {
+        Ok(TableHandle::new(
+            self.client()?.clone(),
+            self.created_table_name(base_name)?.to_string(),
+            self.table_info(base_name)?.clone(),
+        ))
+    }
+
     /// Returns a map of all tables created by this connector.
     /// Keys are the base names from the config, values are the unique created names.
     pub fn get_all_created_table_names(&self) -> &HashMap<String, String> {
@@ -73,17 +136,25 @@ impl DynamodbConnector {
     ///
     /// - Sets up AWS SDK configuration.
     /// - Creates a `aws_sdk_dynamodb::Client`.
+    /// - If `sweep_orphans_older_than` is set, deletes stale tables left behind for the
+    ///   configured base names (see [`crate::sweep_orphans`]).
     /// - Iterates through `config.tables`. For each `TableInfo`:
     ///   - Attempts to create a DynamoDB table with a unique name derived from `TableInfo.table_name`.
+    ///   - Tags it with [`TableConfig::table_tags`] on top of automatic ownership tags
+    ///     (`created-by`, `run-id`, `hostname` and `created-at`).
     ///   - Stores the mapping from the base name to the unique name.
+    ///   - Once active, enables point-in-time recovery and time to live, and adds the
+    ///     configured replicas.
+    /// - With the `auto_scaling` feature and no local `endpoint`, registers the configured
+    ///   auto scaling targets (see [`DynamodbConnector::register_auto_scaling`]).
+    /// - Seeds every table that has a `seed_data_file` (see [`DynamodbConnector::seed`]).
     ///
     /// # Errors
     ///
     /// Returns `Err` if AWS config fails, client creation fails, or any table creation fails.
     pub async fn try_new(config: TableConfig) -> Result<Self> {
         let endpoint = config.endpoint.clone();
-        // Store config for Drop
-        #[cfg(feature = "test_utils")]
+        // Store config for seeding and Drop
         let connector_config = config.clone();
 
         let base_sdk_config_builder = aws_config::defaults(BehaviorVersion::latest()).region(
@@ -102,12 +173,23 @@ impl DynamodbConnector {
         };
         let client = Client::from_conf(dynamodb_config);
 
+        if let Some(secs) = config.sweep_orphans_older_than {
+            let base_names = config.tables.iter().map(|t| t.table_name.clone()).collect();
+            sweep::sweep_orphans(
+                &client,
+                &SweepTarget::BaseNames(base_names),
+                Duration::from_secs(secs),
+            )
+            .await?;
+        }
+
         let mut created_tables = HashMap::new();
 
-        for table_info in config.tables {
+        for table_info in &config.tables {
             let base_table_name = table_info.table_name.clone();
-            let seed_file = table_info.seed_data_file.clone(); // Clone seed file path
-            let mut input = CreateTableInput::try_from(table_info)?;
+            let mut tags = automatic_tags();
+            tags.extend(config.table_tags(table_info));
+            let mut input = CreateTableInput::try_from(table_info.clone())?;
 
             let unique_table_name = format!("{}-{}", base_table_name, xid::new());
             input.table_name = Some(unique_table_name.clone());
@@ -116,10 +198,17 @@ impl DynamodbConnector {
             let create_table_builder = client
                 .create_table()
                 .table_name(&unique_table_name)
+                .set_tags(Some(config::to_tags(&tags)?))
                 .set_key_schema(input.key_schema)
                 .set_attribute_definitions(input.attribute_definitions)
                 .set_global_secondary_indexes(input.global_secondary_indexes)
-                .set_local_secondary_indexes(input.local_secondary_indexes);
+                .set_local_secondary_indexes(input.local_secondary_indexes)
+                .set_sse_specification(input.sse_specification)
+                .set_table_class(input.table_class)
+                .set_deletion_protection_enabled(input.deletion_protection_enabled)
+                .set_stream_specification(input.stream_specification)
+                .set_on_demand_throughput(input.on_demand_throughput)
+                .set_warm_throughput(input.warm_throughput);
 
             let create_table_builder = match input.provisioned_throughput {
                 Some(pt) => create_table_builder.provisioned_throughput(pt),
@@ -139,69 +228,558 @@ impl DynamodbConnector {
 
             created_tables.insert(base_table_name.clone(), unique_table_name.clone());
 
-            // --- Seed Data ---
-            if let Some(file_path) = seed_file {
-                println!(
-                    "[INFO] Seeding data for table '{}' from file '{}'",
-                    unique_table_name, file_path
-                );
-                // Read file content
-                let content = fs::read_to_string(&file_path)
-                    .map_err(|e| DynamoToolsError::SeedFileRead(file_path.clone(), e))?;
-
-                // Parse JSON array
-                let items_json: Vec<Value> = serde_json::from_str(&content)
-                    .map_err(|e| DynamoToolsError::SeedJsonParse(file_path.clone(), e))?;
-
-                // Convert to WriteRequests
-                let mut write_requests = Vec::new();
-                for item_value in items_json {
-                    let item_map: HashMap<String, AttributeValue> =
-                        serde_dynamo::to_item(item_value)?;
-                    let put_request = PutRequest::builder()
-                        .set_item(Some(item_map))
-                        .build()
-                        .map_err(|e| {
-                            DynamoToolsError::Internal(format!("Failed to build PutRequest: {}", e))
-                        })?;
-                    write_requests.push(WriteRequest::builder().put_request(put_request).build());
+            configure_table(&client, table_info, &unique_table_name).await?;
+        }
+
+        let connector = Self {
+            client: Some(client),
+            created_tables,
+            config: connector_config,
+            #[cfg(feature = "auto_scaling")]
+            auto_scaling: None,
+        };
+
+        #[cfg(feature = "auto_scaling")]
+        let connector = {
+            let mut connector = connector;
+            if endpoint.is_none() {
+                let api = aws_sdk_applicationautoscaling::Client::new(&loaded_sdk_config);
+                if connector.register_auto_scaling(&api).await? > 0 {
+                    connector.auto_scaling = Some(api);
                 }
+            } else {
+                println!("[INFO] Skipping auto scaling registration for local endpoint");
+            }
+            connector
+        };
 
-                // Batch write items (chunking by 25)
-                for chunk in write_requests.chunks(25) {
-                    let request_items =
-                        HashMap::from([(unique_table_name.clone(), chunk.to_vec())]);
-                    client
-                        .batch_write_item()
-                        .set_request_items(Some(request_items))
-                        .send()
-                        .await
-                        .map_err(|e| {
-                            DynamoToolsError::SeedBatchWrite(unique_table_name.clone(), e)
-                        })?;
-                    println!(
-                        "[INFO] Wrote batch of {} items to table '{}'",
-                        chunk.len(),
-                        unique_table_name
-                    );
+        // --- Seed Data ---
+        for table_info in &connector.config.tables {
+            if let Some(ref file_path) = table_info.seed_data_file {
+                connector.seed(&table_info.table_name, file_path).await?;
+            }
+        }
+        // --- End Seed Data ---
+
+        Ok(connector)
+    }
+
+    /// Seeds a table created by this connector with the items from a file.
+    ///
+    /// The file may be in any [`DataFormat`]; the format is detected from its
+    /// content. Items are written using the table's configured [`crate::SeedMode`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if `base_name` is not a table created by this connector
+    /// ([`DynamoToolsError::UnknownTable`]), if the file cannot be read or parsed,
+    /// or if writing the items fails.
+    pub async fn seed(&self, base_name: &str, source: impl AsRef<Path>) -> Result<()> {
+        let source = source.as_ref();
+        let table_name = self.created_table_name(base_name)?;
+        println!(
+            "[INFO] Seeding data for table '{}' from file '{}'",
+            table_name,
+            source.display()
+        );
+        let items = seed::read_seed_file(source)?;
+        self.write_seed_items(base_name, items).await
+    }
+
+    /// Seeds a table created by this connector with the given items.
+    ///
+    /// Each item is converted to a DynamoDB item with `serde_dynamo` and written
+    /// using the table's configured [`crate::SeedMode`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if `base_name` is not a table created by this connector
+    /// ([`DynamoToolsError::UnknownTable`]), if an item cannot be converted, or if
+    /// writing the items fails.
+    pub async fn seed_items<T: Serialize>(
+        &self,
+        base_name: &str,
+        items: impl IntoIterator<Item = T>,
+    ) -> Result<()> {
+        let items = items
+            .into_iter()
+            .map(|item| serde_dynamo::to_item(item).map_err(DynamoToolsError::from))
+            .collect::<Result<Vec<_>>>()?;
+        self.write_seed_items(base_name, items).await
+    }
+
+    async fn write_seed_items(&self, base_name: &str, items: Vec<item::Item>) -> Result<()> {
+        let table_name = self.created_table_name(base_name)?;
+        let table_info = self.table_info(base_name)?;
+        seed::write_items(
+            self.client()?,
+            table_name,
+            &table_info.key_names(),
+            items,
+            table_info.seed_mode,
+        )
+        .await
+    }
+
+    /// Exports every item of a table to a file, returning the number of items written.
+    ///
+    /// `table` may be the base name of a table created by this connector or the
+    /// real name of any table. Files written in any [`DataFormat`] can be loaded back
+    /// with [`DynamodbConnector::seed`] or `seed_data_file`. Use
+    /// [`DataFormat::DynamoDbJson`] for a lossless round trip, since plain JSON cannot
+    /// distinguish sets from lists or keep binary values.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if scanning the table fails or the file cannot be written.
+    pub async fn export_table(
+        &self,
+        table: &str,
+        path: impl AsRef<Path>,
+        format: DataFormat,
+    ) -> Result<usize> {
+        self.export_table_segmented(table, path, format, 1).await
+    }
+
+    /// Like [`DynamodbConnector::export_table`], but scans the table with
+    /// `segments` parallel scan segments.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if scanning the table fails or the file cannot be written.
+    pub async fn export_table_segmented(
+        &self,
+        table: &str,
+        path: impl AsRef<Path>,
+        format: DataFormat,
+        segments: i32,
+    ) -> Result<usize> {
+        let path = path.as_ref();
+        let table_name = self.resolve_table_name(table);
+        let items = scan::scan_all(self.client()?, table_name, segments).await?;
+
+        let mut content = Vec::new();
+        item::write_items(&mut content, &items, format)?;
+        fs::write(path, content).map_err(|e| {
+            DynamoToolsError::ExportFileWrite(path.to_string_lossy().to_string(), e)
+        })?;
+        println!(
+            "[INFO] Exported {} items from table '{}' to '{}'",
+            items.len(),
+            table_name,
+            path.display()
+        );
+        Ok(items.len())
+    }
+
+    /// Deletes every item of a table created by this connector, keeping the table itself.
+    ///
+    /// Only the key attributes are scanned, and items are removed with batched
+    /// deletes. Returns the number of deleted items.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if `base_name` is not a table created by this connector
+    /// ([`DynamoToolsError::UnknownTable`]) or if scanning or deleting fails
+    /// ([`DynamoToolsError::BatchWrite`] / [`DynamoToolsError::BatchWriteUnprocessed`]).
+    pub async fn truncate(&self, base_name: &str) -> Result<usize> {
+        let table_name = self.created_table_name(base_name)?;
+        let key_names = self.table_info(base_name)?.key_names();
+        let client = self.client()?;
+
+        let keys = scan::scan_keys(client, table_name, &key_names).await?;
+        let count = keys.len();
+        let requests = keys
+            .into_iter()
+            .map(seed::delete_request)
+            .collect::<Result<Vec<_>>>()?;
+        seed::batch_write(client, table_name, requests, DynamoToolsError::BatchWrite).await?;
+        println!(
+            "[INFO] Truncated table '{}' ({} items deleted)",
+            table_name, count
+        );
+        Ok(count)
+    }
+
+    /// Truncates a table created by this connector and re-applies its configured
+    /// `seed_data_file`, so the table is back to its state right after creation.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if truncating or seeding the table fails.
+    pub async fn reset(&self, base_name: &str) -> Result<()> {
+        self.truncate(base_name).await?;
+        if let Some(ref file_path) = self.table_info(base_name)?.seed_data_file {
+            self.seed(base_name, file_path).await?;
+        }
+        Ok(())
+    }
+
+    /// Captures all items of a table created by this connector in memory.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if `base_name` is not a table created by this connector
+    /// ([`DynamoToolsError::UnknownTable`]) or if scanning fails.
+    pub async fn snapshot(&self, base_name: &str) -> Result<Snapshot> {
+        let table_name = self.created_table_name(base_name)?;
+        let items = scan::scan_all(self.client()?, table_name, 1).await?;
+        Ok(Snapshot::new(base_name, items))
+    }
+
+    /// Reverts a table created by this connector to exactly the state captured in
+    /// `snapshot`.
+    ///
+    /// Items that are not in the snapshot are deleted, and items that are missing
+    /// or differ from the snapshot are rewritten. Unchanged items are left alone.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if the snapshot's table is not a table created by this connector
+    /// ([`DynamoToolsError::UnknownTable`]) or if scanning or writing fails
+    /// ([`DynamoToolsError::BatchWrite`] / [`DynamoToolsError::BatchWriteUnprocessed`]).
+    pub async fn restore(&self, snapshot: &Snapshot) -> Result<()> {
+        let base_name = snapshot.base_name();
+        let table_name = self.created_table_name(base_name)?;
+        let key_names = self.table_info(base_name)?.key_names();
+        let client = self.client()?;
+
+        let mut current = HashMap::new();
+        for item in scan::scan_all(client, table_name, 1).await? {
+            current.insert(item::key_id(&item, &key_names)?, item);
+        }
+
+        let mut requests = Vec::new();
+        for item in snapshot.items() {
+            match current.remove(&item::key_id(item, &key_names)?) {
+                Some(existing) if existing == *item => {}
+                _ => requests.push(seed::put_request(item.clone())?),
+            }
+        }
+        let rewritten = requests.len();
+        let deleted = current.len();
+        for item in current.into_values() {
+            requests.push(seed::delete_request(item::extract_key(&item, &key_names)?)?);
+        }
+        seed::batch_write(client, table_name, requests, DynamoToolsError::BatchWrite).await?;
+        println!(
+            "[INFO] Restored table '{}' ({} items rewritten, {} deleted)",
+            table_name, rewritten, deleted
+        );
+        Ok(())
+    }
+
+    /// Checks the current items of a table created by this connector (typically its
+    /// seed data) against the GSIs and LSIs configured for it.
+    ///
+    /// For each index, every item carrying the index key attributes is looked up by
+    /// querying the index with its key. The returned [`IndexReport`]s list the items
+    /// that could not be found and the non-key attributes that queries through the
+    /// index would miss because of the projection.
+    ///
+    /// GSIs are eventually consistent, so on real AWS run this once the index has
+    /// caught up with recent writes.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if `base_name` is not a table created by this connector
+    /// ([`DynamoToolsError::UnknownTable`]) or if scanning or querying fails.
+    pub async fn check_indexes(&self, base_name: &str) -> Result<Vec<IndexReport>> {
+        let table_name = self.created_table_name(base_name)?;
+        let client = self.client()?;
+        let items = scan::scan_all(client, table_name, 1).await?;
+        let reports =
+            index_check::check_indexes(client, table_name, self.table_info(base_name)?, &items)
+                .await?;
+        for report in reports.iter().filter(|r| !r.is_complete()) {
+            println!("[INFO] Table '{}': {}", table_name, report);
+        }
+        Ok(reports)
+    }
+
+    /// Registers the auto scaling targets and policies configured for every created
+    /// table and its GSIs through `api`. Returns the number of configured targets.
+    ///
+    /// Tables are waited on until `ACTIVE` before registration.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if a table with auto scaling settings is not provisioned
+    /// (checked for every table before anything is registered), waiting for a
+    /// table fails or any auto scaling call fails.
+    #[cfg(feature = "auto_scaling")]
+    pub async fn register_auto_scaling(
+        &self,
+        api: &impl crate::auto_scaling::AutoScalingApi,
+    ) -> Result<usize> {
+        for table_info in &self.config.tables {
+            crate::auto_scaling::check_provisioned(table_info)?;
+        }
+        let mut count = 0;
+        for table_info in &self.config.tables {
+            let table_name = self.created_table_name(&table_info.table_name)?;
+            if crate::auto_scaling::scaling_targets(table_name, table_info).is_empty() {
+                continue;
+            }
+            wait_for_active(self.client()?, table_name).await?;
+            count +=
+                crate::auto_scaling::register_auto_scaling(api, table_name, table_info).await?;
+        }
+        Ok(count)
+    }
+
+    /// Deregisters the auto scaling targets (and their policies) configured for every
+    /// created table through `api`. Returns the number of deregistered targets.
+    ///
+    /// A connector that registered targets itself also deregisters them in
+    /// [`DynamodbConnector::delete_tables`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` with the first failing call; every target is still attempted.
+    #[cfg(feature = "auto_scaling")]
+    pub async fn deregister_auto_scaling(
+        &self,
+        api: &impl crate::auto_scaling::AutoScalingApi,
+    ) -> Result<usize> {
+        let mut count = 0;
+        let mut first_error = None;
+        for table_info in &self.config.tables {
+            let table_name = self.created_table_name(&table_info.table_name)?;
+            match crate::auto_scaling::deregister_auto_scaling(api, table_name, table_info).await {
+                Ok(n) => count += n,
+                Err(e) => {
+                    first_error.get_or_insert(e);
                 }
             }
-            // --- End Seed Data ---
         }
+        match first_error {
+            Some(e) => Err(e),
+            None => Ok(count),
+        }
+    }
 
-        Ok(Self {
-            client: Some(client),
-            created_tables,
-            #[cfg(feature = "test_utils")]
-            config: connector_config,
-        })
+    /// Deletes every table created by this connector, after deregistering the auto
+    /// scaling targets the connector registered, lifting deletion protection and
+    /// removing replicas.
+    ///
+    /// Dropping the connector only deletes tables on a local `endpoint`; on AWS,
+    /// call this at the end of a test instead, or leaked tables (and their replicas
+    /// and scalable targets) outlive it. Deleted tables are forgotten, so a later
+    /// drop does not try again.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` with the first failure; every table is still attempted.
+    pub async fn delete_tables(&mut self) -> Result<()> {
+        let client = self.client()?.clone();
+        let mut first_error = None;
+        let mut deleted = Vec::new();
+        for (base_name, unique_name) in &self.created_tables {
+            let info = self
+                .config
+                .tables
+                .iter()
+                .find(|t| t.table_name == *base_name);
+            let result = teardown_table(
+                &client,
+                #[cfg(feature = "auto_scaling")]
+                self.auto_scaling.as_ref(),
+                unique_name,
+                info,
+            )
+            .await;
+            match result {
+                Ok(()) => deleted.push(base_name.clone()),
+                Err(e) => {
+                    eprintln!("[ERROR] Failed to delete table '{}': {}", unique_name, e);
+                    first_error.get_or_insert(e);
+                }
+            }
+        }
+        for base_name in deleted {
+            self.created_tables.remove(&base_name);
+        }
+        match first_error {
+            Some(e) => Err(e),
+            None => Ok(()),
+        }
+    }
+
+    // Maps a base name to the created table name, passing real table names through
+    fn resolve_table_name<'a>(&'a self, table: &'a str) -> &'a str {
+        self.get_created_table_name(table).unwrap_or(table)
+    }
+
+    // Like `get_created_table_name`, but returns an error for unknown base names
+    fn created_table_name(&self, base_name: &str) -> Result<&str> {
+        self.get_created_table_name(base_name)
+            .ok_or_else(|| DynamoToolsError::UnknownTable(base_name.to_string()))
+    }
+
+    // Returns the configured schema for a table base name
+    fn table_info(&self, base_name: &str) -> Result<&TableInfo> {
+        self.config
+            .tables
+            .iter()
+            .find(|t| t.table_name == base_name)
+            .ok_or_else(|| DynamoToolsError::UnknownTable(base_name.to_string()))
+    }
+}
+
+/// Applies the table settings that `CreateTable` cannot set.
+async fn configure_table(client: &Client, table_info: &TableInfo, table_name: &str) -> Result<()> {
+    if table_info.point_in_time_recovery {
+        wait_for_active(client, table_name).await?;
+        let spec = PointInTimeRecoverySpecification::builder()
+            .point_in_time_recovery_enabled(true)
+            .build()?;
+        client
+            .update_continuous_backups()
+            .table_name(table_name)
+            .point_in_time_recovery_specification(spec)
+            .send()
+            .await
+            .map_err(|e| DynamoToolsError::ContinuousBackups(table_name.to_string(), e))?;
+    }
+    if let Some(ref attribute) = table_info.ttl_attribute {
+        wait_for_active(client, table_name).await?;
+        let spec = TimeToLiveSpecification::builder()
+            .attribute_name(attribute)
+            .enabled(true)
+            .build()?;
+        client
+            .update_time_to_live()
+            .table_name(table_name)
+            .time_to_live_specification(spec)
+            .send()
+            .await
+            .map_err(|e| DynamoToolsError::TimeToLive(table_name.to_string(), e))?;
+    }
+    // Only one replica can be added per update, each once the table is active again
+    for replica in &table_info.replicas {
+        wait_for_active(client, table_name).await?;
+        client
+            .update_table()
+            .table_name(table_name)
+            .replica_updates(replica_create(replica)?)
+            .send()
+            .await
+            .map_err(|e| DynamoToolsError::ReplicaUpdate(table_name.to_string(), e))?;
+        println!(
+            "[INFO] Added replica of '{}' in {}",
+            table_name, replica.region
+        );
+    }
+    if !table_info.replicas.is_empty() {
+        wait_for_active(client, table_name).await?;
+    }
+    Ok(())
+}
+
+/// Builds the replica update that creates `replica`.
+fn replica_create(replica: &Replica) -> Result<ReplicationGroupUpdate> {
+    let mut action = CreateReplicationGroupMemberAction::builder().region_name(&replica.region);
+    for gsi in &replica.gsis {
+        action = action.global_secondary_indexes(gsi.clone().into());
     }
+    let action = action
+        .set_table_class_override(replica.table_class.map(Into::into))
+        .build()?;
+    Ok(ReplicationGroupUpdate::builder().create(action).build())
+}
+
+/// Waits until `table_name` is `ACTIVE`.
+pub(crate) async fn wait_for_active(client: &Client, table_name: &str) -> Result<()> {
+    client
+        .wait_until_table_exists()
+        .table_name(table_name)
+        .wait(TABLE_WAIT_TIMEOUT)
+        .await
+        .map_err(|e| DynamoToolsError::TableWait(table_name.to_string(), Box::new(e)))?;
+    Ok(())
+}
+
+/// Ownership tags added to every table created by a connector.
+///
+/// `run-id` is shared by all connectors of the same process, so tables created
+/// by one test run can be identified together.
+fn automatic_tags() -> BTreeMap<String, String> {
+    static RUN_ID: OnceLock<String> = OnceLock::new();
+    let run_id = RUN_ID.get_or_init(|| xid::new().to_string());
+    let hostname = hostname::get()
+        .map(|h| h.to_string_lossy().to_string())
+        .unwrap_or_else(|_| "unknown".to_string());
+    let created_at = DateTime::from(SystemTime::now())
+        .fmt(DateTimeFormat::DateTime)
+        .unwrap_or_default();
+
+    BTreeMap::from([
+        ("created-by".to_string(), "dynamodb-tools".to_string()),
+        ("run-id".to_string(), run_id.clone()),
+        ("hostname".to_string(), hostname),
+        ("created-at".to_string(), created_at),
+    ])
+}
+
+// Deletes `table_name`: deregisters its scalable targets through `auto_scaling`,
+// lifts the deletion protection and removes the replicas configured in `info` first.
+// Deregistration failures are logged and reported once the table is deleted.
+pub(crate) async fn teardown_table(
+    client: &Client,
+    #[cfg(feature = "auto_scaling")] auto_scaling: Option<
+        &impl crate::auto_scaling::AutoScalingApi,
+    >,
+    table_name: &str,
+    info: Option<&TableInfo>,
+) -> Result<()> {
+    // Scalable targets outlive their table, so remove them first
+    #[cfg(feature = "auto_scaling")]
+    let deregistered = match (auto_scaling, info) {
+        (Some(api), Some(info)) => {
+            crate::auto_scaling::deregister_auto_scaling(api, table_name, info)
+                .await
+                .map(|_| ())
+        }
+        _ => Ok(()),
+    };
+    // These are throwaway tables, so lift the protection we set up
+    if info.is_some_and(|t| t.deletion_protection) {
+        disable_deletion_protection(client, table_name).await?;
+    }
+    let regions: Vec<String> = info
+        .map(|t| t.replicas.iter().map(|r| r.region.clone()).collect())
+        .unwrap_or_default();
+    sweep::remove_replicas(client, table_name, &regions).await?;
+    client
+        .delete_table()
+        .table_name(table_name)
+        .send()
+        .await
+        .map_err(DynamoToolsError::TableDeletion)?;
+    println!("[INFO] Deleted table: {}", table_name);
+    #[cfg(feature = "auto_scaling")]
+    deregistered?;
+    Ok(())
+}
+
+pub(crate) async fn disable_deletion_protection(client: &Client, table_name: &str) -> Result<()> {
+    client
+        .update_table()
+        .table_name(table_name)
+        .deletion_protection_enabled(false)
+        .send()
+        .await
+        .map_err(|e| DynamoToolsError::DeletionProtection(table_name.to_string(), e))?;
+    Ok(())
 }
 
 /// Best-effort table cleanup on drop (requires `test_utils` feature).
 ///
-/// If `delete_on_exit` was true and an endpoint was configured,
-/// attempts to delete all tables created by this connector in background threads.
+/// If `delete_on_exit` was true and an endpoint was configured, attempts to
+/// delete all tables created by this connector in background threads, lifting
+/// deletion protection configured on them first. Tables on AWS are left alone;
+/// use [`DynamodbConnector::delete_tables`] there.
 #[cfg(feature = "test_utils")]
 impl Drop for DynamodbConnector {
     fn drop(&mut self) {
@@ -222,8 +800,14 @@ impl Drop for DynamodbConnector {
                 tables_to_delete.values()
             );
 
-            for (_base_name, unique_name) in tables_to_delete {
+            for (base_name, unique_name) in tables_to_delete {
                 let client_clone = client.clone(); // Clone client for each thread
+                let table_info = self
+                    .config
+                    .tables
+                    .iter()
+                    .find(|t| t.table_name == base_name)
+                    .cloned();
                 std::thread::spawn(move || {
                     let rt = match Runtime::new() {
                         Ok(rt) => rt,
@@ -237,16 +821,17 @@ impl Drop for DynamodbConnector {
                     };
 
                     rt.block_on(async move {
-                        match client_clone
-                            .delete_table()
-                            .table_name(&unique_name)
-                            .send()
-                            .await
+                        // Local endpoints have no auto scaling to deregister
+                        if let Err(e) = teardown_table(
+                            &client_clone,
+                            #[cfg(feature = "auto_scaling")]
+                            None::<&aws_sdk_applicationautoscaling::Client>,
+                            &unique_name,
+                            table_info.as_ref(),
+                        )
+                        .await
                         {
-                            Ok(_) => println!("[INFO] Deleted table: {}", unique_name),
-                            Err(e) => {
-                                eprintln!("[ERROR] Failed to delete table '{}': {}", unique_name, e)
-                            }
+                            eprintln!("[ERROR] Failed to delete table '{}': {}", unique_name, e)
                         }
                     });
                 });
@@ -254,3 +839,38 @@ impl Drop for DynamodbConnector {
         }
     }
 }
+
+#[cfg(all(test, feature = "auto_scaling"))]
+mod tests {
+    use super::*;
+    use crate::auto_scaling::tests::StubApi;
+
+    #[tokio::test]
+    async fn teardown_should_deregister_auto_scaling() {
+        let info = TableInfo::load(
+            r#"
+table_name: users
+pk:
+  name: pk
+  type: S
+throughput: { read: 5, write: 5 }
+auto_scaling:
+  read: { min: 5, max: 100 }
+"#,
+        )
+        .unwrap();
+        // Without a region the delete fails before anything is sent
+        let client = Client::from_conf(
+            aws_sdk_dynamodb::Config::builder()
+                .behavior_version_latest()
+                .build(),
+        );
+        let api = StubApi::default();
+        let result = teardown_table(&client, Some(&api), "users-1", Some(&info)).await;
+        assert!(matches!(result, Err(DynamoToolsError::TableDeletion(_))));
+        assert_eq!(
+            *api.calls.lock().unwrap(),
+            vec!["deregister table/users-1 dynamodb:table:ReadCapacityUnits"]
+        );
+    }
+}
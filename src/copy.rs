@@ -0,0 +1,120 @@
+use crate::error::{DynamoToolsError, Result};
+use crate::item::Item;
+use crate::seed;
+use aws_sdk_dynamodb::Client;
+use aws_sdk_dynamodb::types::AttributeValue;
+use std::collections::HashMap;
+
+/// Options for [`copy_table`].
+#[derive(Debug, Clone, Default)]
+pub struct CopyOptions {
+    /// Optional scan filter expression applied to the source table
+    /// (e.g. `"begins_with(#pk, :prefix)"`).
+    pub filter_expression: Option<String>,
+    /// Expression attribute names used by `filter_expression`.
+    pub expression_attribute_names: HashMap<String, String>,
+    /// Expression attribute values used by `filter_expression`.
+    pub expression_attribute_values: HashMap<String, AttributeValue>,
+    /// Maximum number of items to copy. Copies everything if `None`.
+    pub limit: Option<usize>,
+    /// Attributes to rename while copying, from source name to destination name.
+    /// Use this when the destination table has different key attribute names.
+    pub attribute_mapping: HashMap<String, String>,
+}
+
+/// Copies items from one table to another, possibly across accounts or regions.
+///
+/// The source table is scanned page by page (applying the optional filter and
+/// item limit in `options`), attributes are renamed per `options.attribute_mapping`,
+/// and each page is batch-written into the destination. Existing destination items
+/// with the same key are overwritten. Returns the number of copied items.
+///
+/// # Errors
+///
+/// Returns `Err` if scanning the source or writing to the destination fails
+/// ([`DynamoToolsError::BatchWrite`] / [`DynamoToolsError::BatchWriteUnprocessed`]).
+pub async fn copy_table(
+    source_client: &Client,
+    source_table: &str,
+    dest_client: &Client,
+    dest_table: &str,
+    options: &CopyOptions,
+) -> Result<usize> {
+    let mut copied = 0;
+    let mut start_key: Option<Item> = None;
+    loop {
+        let resp = source_client
+            .scan()
+            .table_name(source_table)
+            .set_filter_expression(options.filter_expression.clone())
+            .set_expression_attribute_names(non_empty(&options.expression_attribute_names))
+            .set_expression_attribute_values(non_empty(&options.expression_attribute_values))
+            .set_exclusive_start_key(start_key.take())
+            .send()
+            .await
+            .map_err(|e| DynamoToolsError::Scan(source_table.to_string(), e))?;
+
+        let mut items = resp.items.unwrap_or_default();
+        if let Some(limit) = options.limit {
+            items.truncate(limit - copied);
+        }
+        copied += items.len();
+
+        let requests = items
+            .into_iter()
+            .map(|item| seed::put_request(rename_attributes(item, &options.attribute_mapping)))
+            .collect::<Result<Vec<_>>>()?;
+        seed::batch_write(
+            dest_client,
+            dest_table,
+            requests,
+            DynamoToolsError::BatchWrite,
+        )
+        .await?;
+
+        match resp.last_evaluated_key {
+            Some(key) if !key.is_empty() && Some(copied) != options.limit => start_key = Some(key),
+            _ => break,
+        }
+    }
+    println!(
+        "[INFO] Copied {} items from table '{}' to table '{}'",
+        copied, source_table, dest_table
+    );
+    Ok(copied)
+}
+
+fn non_empty<V: Clone>(map: &HashMap<String, V>) -> Option<HashMap<String, V>> {
+    (!map.is_empty()).then(|| map.clone())
+}
+
+fn rename_attributes(item: Item, mapping: &HashMap<String, String>) -> Item {
+    item.into_iter()
+        .map(|(name, value)| match mapping.get(&name) {
+            Some(new_name) => (new_name.clone(), value),
+            None => (name, value),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn attributes_should_be_renamed() {
+        let item = HashMap::from([
+            ("pk".to_string(), AttributeValue::S("user_1".to_string())),
+            ("name".to_string(), AttributeValue::S("Alice".to_string())),
+        ]);
+        let mapping = HashMap::from([("pk".to_string(), "user_id".to_string())]);
+
+        let renamed = rename_attributes(item, &mapping);
+        assert_eq!(
+            renamed.get("user_id"),
+            Some(&AttributeValue::S("user_1".to_string()))
+        );
+        assert!(!renamed.contains_key("pk"));
+        assert!(renamed.contains_key("name"));
+    }
+}
@@ -0,0 +1,610 @@
+use crate::error::{DynamoToolsError, Result};
+use crate::expression::attr;
+use crate::item::Item;
+use crate::{AttrType, TableAttr, TableHandle};
+use aws_sdk_dynamodb::types::AttributeValue;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use std::marker::PhantomData;
+
+/// Attribute holding the [`Entity::NAME`] of every item written through [`Entities`].
+///
+/// Partitions holding several entities can be read into an enum tagged with it:
+/// `#[serde(tag = "entity_type")]`.
+pub const ENTITY_TYPE_ATTR: &str = "entity_type";
+
+/// An item type stored in a single-table design, with keys built from its fields.
+///
+/// ```rust,ignore
+/// #[derive(Serialize, Deserialize)]
+/// struct Order {
+///     user_id: String,
+///     order_id: String,
+///     status: String,
+/// }
+///
+/// impl Entity for Order {
+///     const NAME: &'static str = "Order";
+///
+///     fn keys() -> EntityKeys {
+///         EntityKeys::new("USER#{user_id}")
+///             .sk("ORDER#{order_id}")
+///             .gsi("gsi1", "STATUS#{status}", Some("ORDER#{order_id}"))
+///     }
+/// }
+/// ```
+pub trait Entity: Serialize + DeserializeOwned {
+    /// The entity type, stored in [`ENTITY_TYPE_ATTR`].
+    const NAME: &'static str;
+
+    /// Returns the key templates of the entity.
+    fn keys() -> EntityKeys;
+}
+
+/// Key templates of an [`Entity`].
+///
+/// A template is a string where `{field}` is replaced by the value of a string or
+/// number field of the entity, e.g. `USER#{id}`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EntityKeys {
+    pk: String,
+    sk: Option<String>,
+    gsis: Vec<(String, String, Option<String>)>,
+}
+
+impl EntityKeys {
+    /// Creates the keys with the template of the table partition key.
+    pub fn new(pk: impl Into<String>) -> Self {
+        Self {
+            pk: pk.into(),
+            sk: None,
+            gsis: Vec::new(),
+        }
+    }
+
+    /// Sets the template of the table sort key.
+    pub fn sk(mut self, template: impl Into<String>) -> Self {
+        self.sk = Some(template.into());
+        self
+    }
+
+    /// Adds the templates of the partition and sort keys of GSI `index`.
+    pub fn gsi(
+        mut self,
+        index: impl Into<String>,
+        pk: impl Into<String>,
+        sk: Option<&str>,
+    ) -> Self {
+        self.gsis
+            .push((index.into(), pk.into(), sk.map(str::to_string)));
+        self
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Part {
+    Literal(String),
+    Field(String),
+}
+
+// A parsed key template such as `USER#{id}`
+#[derive(Debug, Clone, PartialEq)]
+struct KeyTemplate {
+    template: String,
+    parts: Vec<Part>,
+}
+
+impl KeyTemplate {
+    fn parse(template: &str) -> std::result::Result<Self, String> {
+        let mut parts = Vec::new();
+        let mut rest = template;
+        while let Some(start) = rest.find('{') {
+            if start > 0 {
+                parts.push(Part::Literal(rest[..start].to_string()));
+            }
+            let Some(len) = rest[start..].find('}') else {
+                return Err(format!("unclosed '{{' in key template '{}'", template));
+            };
+            let field = &rest[start + 1..start + len];
+            if field.is_empty() || field.contains('{') {
+                return Err(format!("invalid field in key template '{}'", template));
+            }
+            parts.push(Part::Field(field.to_string()));
+            rest = &rest[start + len + 1..];
+        }
+        if rest.contains('}') {
+            return Err(format!("unopened '}}' in key template '{}'", template));
+        }
+        if !rest.is_empty() {
+            parts.push(Part::Literal(rest.to_string()));
+        }
+        Ok(Self {
+            template: template.to_string(),
+            parts,
+        })
+    }
+
+    // The literal text before the first field, shared by every rendered key
+    fn prefix(&self) -> &str {
+        match self.parts.first() {
+            Some(Part::Literal(prefix)) => prefix,
+            _ => "",
+        }
+    }
+
+    // Whether `item` holds every field of the template (`None` fields are null)
+    fn has_fields(&self, item: &Item) -> bool {
+        self.parts.iter().all(|part| match part {
+            Part::Literal(_) => true,
+            Part::Field(field) => item.get(field).is_some_and(|v| !v.is_null()),
+        })
+    }
+
+    fn render(&self, entity: &str, item: &Item) -> Result<String> {
+        let mut out = String::new();
+        for part in &self.parts {
+            match part {
+                Part::Literal(literal) => out.push_str(literal),
+                Part::Field(field) => match item.get(field) {
+                    Some(AttributeValue::S(s)) => out.push_str(s),
+                    Some(AttributeValue::N(n)) => out.push_str(n),
+                    Some(_) => {
+                        return Err(DynamoToolsError::InvalidEntity(
+                            entity.to_string(),
+                            format!(
+                                "field '{}' of key template '{}' must be a string or a number",
+                                field, self.template
+                            ),
+                        ));
+                    }
+                    None => {
+                        return Err(DynamoToolsError::MissingField(format!(
+                            "Field '{}' of key template '{}'",
+                            field, self.template
+                        )));
+                    }
+                },
+            }
+        }
+        Ok(out)
+    }
+}
+
+// A key attribute and the template producing its value
+#[derive(Debug, Clone)]
+struct KeyMapping {
+    attr: String,
+    template: KeyTemplate,
+}
+
+#[derive(Debug, Clone)]
+struct GsiMapping {
+    index: String,
+    pk: KeyMapping,
+    sk: Option<KeyMapping>,
+}
+
+/// Typed access to the items of one [`Entity`] in a table.
+///
+/// Obtained with [`TableHandle::entities`], which checks the key templates
+/// against the table schema. Items are written with their rendered table and
+/// GSI keys and with [`ENTITY_TYPE_ATTR`] set to [`Entity::NAME`].
+#[derive(Debug)]
+pub struct Entities<'a, E> {
+    handle: &'a TableHandle,
+    pk: KeyMapping,
+    sk: Option<KeyMapping>,
+    gsis: Vec<GsiMapping>,
+    entity: PhantomData<fn() -> E>,
+}
+
+impl<'a, E: Entity> Entities<'a, E> {
+    fn new(handle: &'a TableHandle) -> Result<Self> {
+        let keys = E::keys();
+        let info = handle.info();
+        let (pk, sk) = mapping_pair("the table", &info.pk, info.sk.as_ref(), &keys.pk, &keys.sk)
+            .map_err(invalid::<E>)?;
+        let mut gsis = Vec::new();
+        for (index, pk, sk) in &keys.gsis {
+            let gsi = info
+                .gsis
+                .iter()
+                .find(|gsi| gsi.name == *index)
+                .ok_or_else(|| invalid::<E>(format!("unknown GSI '{}'", index)))?;
+            let (pk, sk) = mapping_pair(
+                &format!("GSI '{}'", index),
+                &gsi.pk,
+                gsi.sk.as_ref(),
+                pk,
+                sk,
+            )
+            .map_err(invalid::<E>)?;
+            gsis.push(GsiMapping {
+                index: index.clone(),
+                pk,
+                sk,
+            });
+        }
+        Ok(Self {
+            handle,
+            pk,
+            sk,
+            gsis,
+            entity: PhantomData,
+        })
+    }
+
+    /// Writes `entity` with its table and GSI keys, replacing any item with the same key.
+    ///
+    /// GSI keys are only written when the entity has every field of their templates
+    /// (`None` counts as missing), so optional fields make sparse indexes.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if the entity cannot be converted ([`DynamoToolsError::ItemConversion`]),
+    /// misses a field used by a table key template ([`DynamoToolsError::MissingField`]) or
+    /// `PutItem` fails ([`DynamoToolsError::PutItem`]).
+    pub async fn put(&self, entity: &E) -> Result<()> {
+        let item = self.to_item(entity)?;
+        self.handle
+            .client()
+            .put_item()
+            .table_name(self.handle.name())
+            .set_item(Some(item))
+            .send()
+            .await
+            .map_err(|e| DynamoToolsError::PutItem(self.handle.name().to_string(), e))?;
+        Ok(())
+    }
+
+    /// Reads the entity whose key is rendered from `fields`, any value serializing to
+    /// a map holding the fields used by the table key templates.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if a template field is missing ([`DynamoToolsError::MissingField`]),
+    /// `GetItem` fails ([`DynamoToolsError::GetItem`]) or the item cannot be converted.
+    pub async fn get<K: Serialize>(&self, fields: &K) -> Result<Option<E>> {
+        let resp = self
+            .handle
+            .client()
+            .get_item()
+            .table_name(self.handle.name())
+            .set_key(Some(self.key(&self.encode(fields)?)?))
+            .consistent_read(true)
+            .send()
+            .await
+            .map_err(|e| DynamoToolsError::GetItem(self.handle.name().to_string(), e))?;
+        resp.item.map(|item| self.decode(item)).transpose()
+    }
+
+    /// Deletes the entity whose key is rendered from `fields` (see [`Entities::get`]).
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if a template field is missing ([`DynamoToolsError::MissingField`])
+    /// or `DeleteItem` fails ([`DynamoToolsError::DeleteItem`]).
+    pub async fn delete<K: Serialize>(&self, fields: &K) -> Result<()> {
+        self.handle
+            .client()
+            .delete_item()
+            .table_name(self.handle.name())
+            .set_key(Some(self.key(&self.encode(fields)?)?))
+            .send()
+            .await
+            .map_err(|e| DynamoToolsError::DeleteItem(self.handle.name().to_string(), e))?;
+        Ok(())
+    }
+
+    /// Returns the entities of this type in the partition rendered from `fields`,
+    /// which only needs the fields of the partition key template.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if a template field is missing, `Query` fails or an item cannot
+    /// be converted.
+    pub async fn list<K: Serialize>(&self, fields: &K) -> Result<Vec<E>> {
+        let pk = self.pk.template.render(E::NAME, &self.encode(fields)?)?;
+        let query = self.handle.query_builder().pk_eq(pk);
+        let query = match &self.sk {
+            Some(sk) if !sk.template.prefix().is_empty() => {
+                query.sk_begins_with(sk.template.prefix())
+            }
+            _ => query,
+        };
+        self.of_type(query).collect().await
+    }
+
+    /// Returns the entities of this type found in GSI `index` under the partition
+    /// rendered from `fields`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if the entity has no templates for `index`
+    /// ([`DynamoToolsError::InvalidQuery`]), a template field is missing, `Query`
+    /// fails or an item cannot be converted.
+    pub async fn query_index<K: Serialize>(&self, index: &str, fields: &K) -> Result<Vec<E>> {
+        let gsi = self
+            .gsis
+            .iter()
+            .find(|gsi| gsi.index == index)
+            .ok_or_else(|| {
+                DynamoToolsError::InvalidQuery(
+                    self.handle.name().to_string(),
+                    format!("entity '{}' has no keys for index '{}'", E::NAME, index),
+                )
+            })?;
+        let pk = gsi.pk.template.render(E::NAME, &self.encode(fields)?)?;
+        let query = self.handle.query_builder().index(index).pk_eq(pk);
+        let query = match &gsi.sk {
+            Some(sk) if !sk.template.prefix().is_empty() => {
+                query.sk_begins_with(sk.template.prefix())
+            }
+            _ => query,
+        };
+        self.of_type(query).collect().await
+    }
+
+    /// Returns every item in the partition rendered from `fields`, whatever its
+    /// entity type, converted to `T`, usually an enum tagged with
+    /// [`ENTITY_TYPE_ATTR`]:
+    ///
+    /// ```rust,ignore
+    /// #[derive(Deserialize)]
+    /// #[serde(tag = "entity_type")]
+    /// enum UserItem {
+    ///     User(User),
+    ///     Order(Order),
+    /// }
+    ///
+    /// let items: Vec<UserItem> = users.entities::<User>()?.partition(&json!({ "id": "1" })).await?;
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if a template field is missing, `Query` fails or an item cannot
+    /// be converted to `T`.
+    pub async fn partition<K: Serialize, T: DeserializeOwned>(&self, fields: &K) -> Result<Vec<T>> {
+        let pk = self.pk.template.render(E::NAME, &self.encode(fields)?)?;
+        self.handle.query_builder().pk_eq(pk).collect().await
+    }
+
+    fn of_type(&self, query: crate::QueryBuilder<'a>) -> crate::QueryBuilder<'a> {
+        query.filter_condition(&attr(ENTITY_TYPE_ATTR).eq(E::NAME))
+    }
+
+    // Converts `entity` and adds its key attributes and entity type
+    fn to_item(&self, entity: &E) -> Result<Item> {
+        let mut item = self.encode(entity)?;
+        let mut keys = self.key(&item)?;
+        for gsi in &self.gsis {
+            let mappings = [Some(&gsi.pk), gsi.sk.as_ref()].into_iter().flatten();
+            // Sparse index: an entity lacking a field of the GSI keys is left out of it
+            if !mappings
+                .clone()
+                .all(|mapping| mapping.template.has_fields(&item))
+            {
+                continue;
+            }
+            for mapping in mappings {
+                keys.insert(
+                    mapping.attr.clone(),
+                    AttributeValue::S(mapping.template.render(E::NAME, &item)?),
+                );
+            }
+        }
+        item.extend(keys);
+        item.insert(
+            ENTITY_TYPE_ATTR.to_string(),
+            AttributeValue::S(E::NAME.to_string()),
+        );
+        Ok(item)
+    }
+
+    // Renders the primary key from the fields in `item`
+    fn key(&self, item: &Item) -> Result<Item> {
+        [Some(&self.pk), self.sk.as_ref()]
+            .into_iter()
+            .flatten()
+            .map(|mapping| {
+                let value = mapping.template.render(E::NAME, item)?;
+                Ok((mapping.attr.clone(), AttributeValue::S(value)))
+            })
+            .collect()
+    }
+
+    fn encode<T: Serialize>(&self, value: &T) -> Result<Item> {
+        serde_dynamo::to_item(value)
+            .map_err(|e| DynamoToolsError::ItemConversion(self.handle.name().to_string(), e))
+    }
+
+    fn decode(&self, item: Item) -> Result<E> {
+        serde_dynamo::from_item(item)
+            .map_err(|e| DynamoToolsError::ItemConversion(self.handle.name().to_string(), e))
+    }
+}
+
+impl TableHandle {
+    /// Returns typed access to the items of entity `E`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` ([`DynamoToolsError::InvalidEntity`]) if a template is malformed,
+    /// names an unknown GSI, is missing for or given to a key the schema lacks, or
+    /// targets a key attribute that is not a string.
+    pub fn entities<E: Entity>(&self) -> Result<Entities<'_, E>> {
+        Entities::new(self)
+    }
+}
+
+fn invalid<E: Entity>(reason: String) -> DynamoToolsError {
+    DynamoToolsError::InvalidEntity(E::NAME.to_string(), reason)
+}
+
+// Pairs the partition and sort key templates with the key attributes of `target`
+fn mapping_pair(
+    target: &str,
+    pk_attr: &TableAttr,
+    sk_attr: Option<&TableAttr>,
+    pk: &str,
+    sk: &Option<String>,
+) -> std::result::Result<(KeyMapping, Option<KeyMapping>), String> {
+    let pk = mapping(pk_attr, pk)?;
+    let sk = match (sk_attr, sk) {
+        (Some(attr), Some(template)) => Some(mapping(attr, template)?),
+        (None, None) => None,
+        (Some(attr), None) => {
+            return Err(format!(
+                "no template for sort key '{}' of {}",
+                attr.name, target
+            ));
+        }
+        (None, Some(template)) => {
+            return Err(format!(
+                "sort key template '{}' given, but {} has no sort key",
+                template, target
+            ));
+        }
+    };
+    Ok((pk, sk))
+}
+
+fn mapping(attr: &TableAttr, template: &str) -> std::result::Result<KeyMapping, String> {
+    if attr.attr_type != AttrType::S {
+        return Err(format!(
+            "key attribute '{}' must be a string to hold template '{}'",
+            attr.name, template
+        ));
+    }
+    Ok(KeyMapping {
+        attr: attr.name.clone(),
+        template: KeyTemplate::parse(template)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TableConfig;
+    use aws_sdk_dynamodb::Client;
+    use serde::Deserialize;
+    use serde_json::json;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Order {
+        user_id: String,
+        order_id: u32,
+        status: Option<String>,
+    }
+
+    impl Entity for Order {
+        const NAME: &'static str = "Order";
+
+        fn keys() -> EntityKeys {
+            EntityKeys::new("USER#{user_id}")
+                .sk("ORDER#{order_id}")
+                .gsi("gsi1", "STATUS#{status}", Some("ORDER#{order_id}"))
+        }
+    }
+
+    #[derive(Debug, Serialize, Deserialize)]
+    struct Orphan {
+        id: String,
+    }
+
+    impl Entity for Orphan {
+        const NAME: &'static str = "Orphan";
+
+        fn keys() -> EntityKeys {
+            EntityKeys::new("ORPHAN#{id}").gsi("gsi9", "X", None)
+        }
+    }
+
+    fn handle() -> TableHandle {
+        let config = TableConfig::load_from_file("fixtures/dev.yml").unwrap();
+        let client_config = aws_sdk_dynamodb::Config::builder()
+            .behavior_version_latest()
+            .build();
+        TableHandle::new(
+            Client::from_conf(client_config),
+            "users-test".to_string(),
+            config.tables[0].clone(),
+        )
+    }
+
+    fn s(value: &str) -> AttributeValue {
+        AttributeValue::S(value.to_string())
+    }
+
+    #[test]
+    fn key_templates_should_parse_and_render() {
+        let template = KeyTemplate::parse("USER#{id}#{n}").unwrap();
+        assert_eq!(template.prefix(), "USER#");
+        let item = serde_dynamo::to_item(json!({ "id": "a", "n": 2 })).unwrap();
+        assert_eq!(template.render("User", &item).unwrap(), "USER#a#2");
+
+        let err = template
+            .render(
+                "User",
+                &serde_dynamo::to_item(json!({ "id": "a" })).unwrap(),
+            )
+            .unwrap_err();
+        assert!(matches!(err, DynamoToolsError::MissingField(_)));
+        assert_eq!(KeyTemplate::parse("{id}").unwrap().prefix(), "");
+        for template in ["USER#{id", "USER#{}", "USER}"] {
+            assert!(KeyTemplate::parse(template).is_err(), "{}", template);
+        }
+    }
+
+    #[test]
+    fn entity_items_should_carry_rendered_keys() {
+        let handle = handle();
+        let orders = handle.entities::<Order>().unwrap();
+        let order = Order {
+            user_id: "u1".to_string(),
+            order_id: 7,
+            status: Some("open".to_string()),
+        };
+        let item = orders.to_item(&order).unwrap();
+        assert_eq!(item["pk"], s("USER#u1"));
+        assert_eq!(item["sk"], s("ORDER#7"));
+        assert_eq!(item["gsi1pk"], s("STATUS#open"));
+        assert_eq!(item["gsi1sk"], s("ORDER#7"));
+        assert_eq!(item[ENTITY_TYPE_ATTR], s("Order"));
+        assert_eq!(orders.decode(item).unwrap(), order);
+
+        let key = orders
+            .key(
+                &orders
+                    .encode(&json!({ "user_id": "u1", "order_id": 7 }))
+                    .unwrap(),
+            )
+            .unwrap();
+        assert_eq!(key.len(), 2);
+        assert_eq!(key["sk"], s("ORDER#7"));
+    }
+
+    #[test]
+    fn entities_without_gsi_fields_should_stay_out_of_the_index() {
+        let handle = handle();
+        let orders = handle.entities::<Order>().unwrap();
+        let order = Order {
+            user_id: "u1".to_string(),
+            order_id: 8,
+            status: None,
+        };
+        let item = orders.to_item(&order).unwrap();
+        assert_eq!(item["sk"], s("ORDER#8"));
+        assert!(!item.contains_key("gsi1pk"));
+        assert!(!item.contains_key("gsi1sk"));
+        assert_eq!(orders.decode(item).unwrap(), order);
+    }
+
+    #[test]
+    fn entity_keys_should_match_the_schema() {
+        let err = handle().entities::<Orphan>().unwrap_err();
+        assert!(
+            matches!(err, DynamoToolsError::InvalidEntity(ref name, ref msg) if name == "Orphan" && msg.contains("sort key 'sk'"))
+        );
+    }
+}
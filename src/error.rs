@@ -2,8 +2,11 @@ use aws_sdk_dynamodb::{
     error::SdkError,
     operation::{
         create_table::CreateTableError, delete_table::DeleteTableError,
-        describe_table::DescribeTableError,
+        describe_table::DescribeTableError, list_tables::ListTablesError,
+        update_continuous_backups::UpdateContinuousBackupsError, update_table::UpdateTableError,
+        update_time_to_live::UpdateTimeToLiveError,
     },
+    waiters::table_exists::WaitUntilTableExistsError,
 };
 use thiserror::Error;
 
@@ -32,6 +35,27 @@ pub enum DynamoToolsError {
     #[error("AWS SDK error during table description: {0}")]
     TableDescribe(#[from] SdkError<DescribeTableError>),
 
+    #[error("AWS SDK error while listing tables: {0}")]
+    ListTables(#[from] SdkError<ListTablesError>),
+
+    #[error("Timed out or failed waiting for table '{0}' to become active: {1}")]
+    TableWait(String, #[source] Box<WaitUntilTableExistsError>),
+
+    #[error("AWS SDK error while enabling continuous backups on table '{0}': {1}")]
+    ContinuousBackups(String, SdkError<UpdateContinuousBackupsError>),
+
+    #[error("AWS SDK error while enabling time to live on table '{0}': {1}")]
+    TimeToLive(String, SdkError<UpdateTimeToLiveError>),
+
+    #[error("AWS SDK error while disabling deletion protection on table '{0}': {1}")]
+    DeletionProtection(String, SdkError<UpdateTableError>),
+
+    #[error("AWS SDK error while updating replicas of table '{0}': {1}")]
+    ReplicaUpdate(String, SdkError<UpdateTableError>),
+
+    #[error("Failed to configure auto scaling for '{0}': {1}")]
+    AutoScaling(String, #[source] Box<dyn std::error::Error + Send + Sync>),
+
     #[error("Failed to read seed data file '{0}': {1}")]
     SeedFileRead(String, #[source] std::io::Error),
 
@@ -41,12 +65,105 @@ pub enum DynamoToolsError {
     #[error("Failed to convert seed data item to DynamoDB format: {0}")]
     SeedDynamoConversion(#[from] serde_dynamo::Error),
 
+    #[error("Gave up writing {1} unprocessed items to table '{0}' after repeated retries")]
+    BatchWriteUnprocessed(String, usize),
+
     #[error("Failed to batch write seed data to table '{0}': {1}")]
     SeedBatchWrite(
         String,
         SdkError<aws_sdk_dynamodb::operation::batch_write_item::BatchWriteItemError>,
     ),
 
+    #[error("Failed to put seed item into table '{0}': {1}")]
+    SeedPut(
+        String,
+        SdkError<aws_sdk_dynamodb::operation::put_item::PutItemError>,
+    ),
+
+    #[error("Failed to transactionally write seed data to table '{0}': {1}")]
+    SeedTransactWrite(
+        String,
+        SdkError<aws_sdk_dynamodb::operation::transact_write_items::TransactWriteItemsError>,
+    ),
+
+    #[error("AWS SDK error while batch writing items to table '{0}': {1}")]
+    BatchWrite(
+        String,
+        SdkError<aws_sdk_dynamodb::operation::batch_write_item::BatchWriteItemError>,
+    ),
+
+    #[error("Failed to read snapshot file '{0}': {1}")]
+    SnapshotRead(String, #[source] std::io::Error),
+
+    #[error("Failed to parse snapshot file '{0}': {1}")]
+    SnapshotParse(String, #[source] serde_json::Error),
+
+    #[error("Failed to write snapshot file '{0}': {1}")]
+    SnapshotWrite(String, #[source] std::io::Error),
+
+    #[error("Invalid DynamoDB JSON: {0}")]
+    InvalidDynamoDbJson(String),
+
+    #[error("AWS SDK error while scanning table '{0}': {1}")]
+    Scan(
+        String,
+        SdkError<aws_sdk_dynamodb::operation::scan::ScanError>,
+    ),
+
+    #[error("AWS SDK error while querying table '{0}': {1}")]
+    Query(
+        String,
+        SdkError<aws_sdk_dynamodb::operation::query::QueryError>,
+    ),
+
+    #[error("AWS SDK error while putting an item into table '{0}': {1}")]
+    PutItem(
+        String,
+        SdkError<aws_sdk_dynamodb::operation::put_item::PutItemError>,
+    ),
+
+    #[error("AWS SDK error while getting an item from table '{0}': {1}")]
+    GetItem(
+        String,
+        SdkError<aws_sdk_dynamodb::operation::get_item::GetItemError>,
+    ),
+
+    #[error("AWS SDK error while deleting an item from table '{0}': {1}")]
+    DeleteItem(
+        String,
+        SdkError<aws_sdk_dynamodb::operation::delete_item::DeleteItemError>,
+    ),
+
+    #[error("Invalid expression: {0}")]
+    InvalidExpression(String),
+
+    #[error("AWS SDK error while updating an item of table '{0}': {1}")]
+    UpdateItem(
+        String,
+        SdkError<aws_sdk_dynamodb::operation::update_item::UpdateItemError>,
+    ),
+
+    #[error("Invalid entity '{0}': {1}")]
+    InvalidEntity(String, String),
+
+    #[error("Invalid query on table '{0}': {1}")]
+    InvalidQuery(String, String),
+
+    #[error("Failed to convert an item of table '{0}': {1}")]
+    ItemConversion(String, #[source] serde_dynamo::Error),
+
+    #[error("Failed to write export file '{0}': {1}")]
+    ExportFileWrite(String, #[source] std::io::Error),
+
+    #[error("Failed to render template: {0}")]
+    TemplateRender(String),
+
+    #[error("Failed to parse template: {0}")]
+    TemplateParse(String),
+
+    #[error("Table '{0}' was not created by this connector")]
+    UnknownTable(String),
+
     #[error("Internal error: {0}")]
     Internal(String),
 }
@@ -0,0 +1,740 @@
+use crate::error::{DynamoToolsError, Result};
+use aws_sdk_dynamodb::types::AttributeValue;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::ops::Not;
+
+// DynamoDB reserved words, which cannot appear as attribute names in expressions.
+// Sorted, for binary search.
+#[rustfmt::skip]
+const RESERVED_WORDS: &[&str] = &[
+    "ABORT", "ABSOLUTE", "ACTION", "ADD", "AFTER", "AGENT", "AGGREGATE", "ALL", "ALLOCATE", "ALTER",
+    "ANALYZE", "AND", "ANY", "ARCHIVE", "ARE", "ARRAY", "AS", "ASC", "ASCII", "ASENSITIVE",
+    "ASSERTION", "ASYMMETRIC", "AT", "ATOMIC", "ATTACH", "ATTRIBUTE", "AUTH", "AUTHORIZATION",
+    "AUTHORIZE", "AUTO", "AVG", "BACK", "BACKUP", "BASE", "BATCH", "BEFORE", "BEGIN", "BETWEEN",
+    "BIGINT", "BINARY", "BIT", "BLOB", "BLOCK", "BOOLEAN", "BOTH", "BREADTH", "BUCKET", "BULK",
+    "BY", "BYTE", "CALL", "CALLED", "CALLING", "CAPACITY", "CASCADE", "CASCADED", "CASE", "CAST",
+    "CATALOG", "CHAR", "CHARACTER", "CHECK", "CLASS", "CLOB", "CLOSE", "CLUSTER", "CLUSTERED",
+    "CLUSTERING", "CLUSTERS", "COALESCE", "COLLATE", "COLLATION", "COLLECTION", "COLUMN", "COLUMNS",
+    "COMBINE", "COMMENT", "COMMIT", "COMPACT", "COMPILE", "COMPRESS", "CONDITION", "CONFLICT",
+    "CONNECT", "CONNECTION", "CONSISTENCY", "CONSISTENT", "CONSTRAINT", "CONSTRAINTS",
+    "CONSTRUCTOR", "CONSUMED", "CONTINUE", "CONVERT", "COPY", "CORRESPONDING", "COUNT", "COUNTER",
+    "CREATE", "CROSS", "CUBE", "CURRENT", "CURSOR", "CYCLE", "DATA", "DATABASE", "DATE", "DATETIME",
+    "DAY", "DEALLOCATE", "DEC", "DECIMAL", "DECLARE", "DEFAULT", "DEFERRABLE", "DEFERRED", "DEFINE",
+    "DEFINED", "DEFINITION", "DELETE", "DELIMITED", "DEPTH", "DEREF", "DESC", "DESCRIBE",
+    "DESCRIPTOR", "DETACH", "DETERMINISTIC", "DIAGNOSTICS", "DIRECTORIES", "DISABLE", "DISCONNECT",
+    "DISTINCT", "DISTRIBUTE", "DO", "DOMAIN", "DOUBLE", "DROP", "DUMP", "DURATION", "DYNAMIC",
+    "EACH", "ELEMENT", "ELSE", "ELSEIF", "EMPTY", "ENABLE", "END", "EQUAL", "EQUALS", "ERROR",
+    "ESCAPE", "ESCAPED", "EVAL", "EVALUATE", "EXCEEDED", "EXCEPT", "EXCEPTION", "EXCEPTIONS",
+    "EXCLUSIVE", "EXEC", "EXECUTE", "EXISTS", "EXIT", "EXPLAIN", "EXPLODE", "EXPORT", "EXPRESSION",
+    "EXTENDED", "EXTERNAL", "EXTRACT", "FAIL", "FALSE", "FAMILY", "FETCH", "FIELDS", "FILE",
+    "FILTER", "FILTERING", "FINAL", "FINISH", "FIRST", "FIXED", "FLATTERN", "FLOAT", "FOR", "FORCE",
+    "FOREIGN", "FORMAT", "FORWARD", "FOUND", "FREE", "FROM", "FULL", "FUNCTION", "FUNCTIONS",
+    "GENERAL", "GENERATE", "GET", "GLOB", "GLOBAL", "GO", "GOTO", "GRANT", "GREATER", "GROUP",
+    "GROUPING", "HANDLER", "HASH", "HAVE", "HAVING", "HEAP", "HIDDEN", "HOLD", "HOUR", "IDENTIFIED",
+    "IDENTITY", "IF", "IGNORE", "IMMEDIATE", "IMPORT", "IN", "INCLUDING", "INCLUSIVE", "INCREMENT",
+    "INCREMENTAL", "INDEX", "INDEXED", "INDEXES", "INDICATOR", "INFINITE", "INITIALLY", "INLINE",
+    "INNER", "INNTER", "INOUT", "INPUT", "INSENSITIVE", "INSERT", "INSTEAD", "INT", "INTEGER",
+    "INTERSECT", "INTERVAL", "INTO", "INVALIDATE", "IS", "ISOLATION", "ITEM", "ITEMS", "ITERATE",
+    "JOIN", "KEY", "KEYS", "LAG", "LANGUAGE", "LARGE", "LAST", "LATERAL", "LEAD", "LEADING",
+    "LEAVE", "LEFT", "LENGTH", "LESS", "LEVEL", "LIKE", "LIMIT", "LIMITED", "LINES", "LIST", "LOAD",
+    "LOCAL", "LOCALTIME", "LOCALTIMESTAMP", "LOCATION", "LOCATOR", "LOCK", "LOCKS", "LOG", "LOGED",
+    "LONG", "LOOP", "LOWER", "MAP", "MATCH", "MATERIALIZED", "MAX", "MAXLEN", "MEMBER", "MERGE",
+    "METHOD", "METRICS", "MIN", "MINUS", "MINUTE", "MISSING", "MOD", "MODE", "MODIFIES", "MODIFY",
+    "MODULE", "MONTH", "MULTI", "MULTISET", "NAME", "NAMES", "NATIONAL", "NATURAL", "NCHAR",
+    "NCLOB", "NEW", "NEXT", "NO", "NONE", "NOT", "NULL", "NULLIF", "NUMBER", "NUMERIC", "OBJECT",
+    "OF", "OFFLINE", "OFFSET", "OLD", "ON", "ONLINE", "ONLY", "OPAQUE", "OPEN", "OPERATOR",
+    "OPTION", "OR", "ORDER", "ORDINALITY", "OTHER", "OTHERS", "OUT", "OUTER", "OUTPUT", "OVER",
+    "OVERLAPS", "OVERRIDE", "OWNER", "PAD", "PARALLEL", "PARAMETER", "PARAMETERS", "PARTIAL",
+    "PARTITION", "PARTITIONED", "PARTITIONS", "PATH", "PERCENT", "PERCENTILE", "PERMISSION",
+    "PERMISSIONS", "PIPE", "PIPELINED", "PLAN", "POOL", "POSITION", "PRECISION", "PREPARE",
+    "PRESERVE", "PRIMARY", "PRIOR", "PRIVATE", "PRIVILEGES", "PROCEDURE", "PROCESSED", "PROJECT",
+    "PROJECTION", "PROPERTY", "PROVISIONING", "PUBLIC", "PUT", "QUERY", "QUIT", "QUORUM", "RAISE",
+    "RANDOM", "RANGE", "RANK", "RAW", "READ", "READS", "REAL", "REBUILD", "RECORD", "RECURSIVE",
+    "REDUCE", "REF", "REFERENCE", "REFERENCES", "REFERENCING", "REGEXP", "REGION", "REINDEX",
+    "RELATIVE", "RELEASE", "REMAINDER", "RENAME", "REPEAT", "REPLACE", "REQUEST", "RESET",
+    "RESIGNAL", "RESOURCE", "RESPONSE", "RESTORE", "RESTRICT", "RESULT", "RETURN", "RETURNING",
+    "RETURNS", "REVERSE", "REVOKE", "RIGHT", "ROLE", "ROLES", "ROLLBACK", "ROLLUP", "ROUTINE",
+    "ROW", "ROWS", "RULE", "RULES", "SAMPLE", "SATISFIES", "SAVE", "SAVEPOINT", "SCAN", "SCHEMA",
+    "SCOPE", "SCROLL", "SEARCH", "SECOND", "SECTION", "SEGMENT", "SEGMENTS", "SELECT", "SELF",
+    "SEMI", "SENSITIVE", "SEPARATE", "SEQUENCE", "SERIALIZABLE", "SESSION", "SET", "SETS", "SHARD",
+    "SHARE", "SHARED", "SHORT", "SHOW", "SIGNAL", "SIMILAR", "SIZE", "SKEWED", "SMALLINT",
+    "SNAPSHOT", "SOME", "SOURCE", "SPACE", "SPACES", "SPARSE", "SPECIFIC", "SPECIFICTYPE", "SPLIT",
+    "SQL", "SQLCODE", "SQLERROR", "SQLEXCEPTION", "SQLSTATE", "SQLWARNING", "START", "STATE",
+    "STATIC", "STATUS", "STORAGE", "STORE", "STORED", "STREAM", "STRING", "STRUCT", "STYLE", "SUB",
+    "SUBMULTISET", "SUBPARTITION", "SUBSTRING", "SUBTYPE", "SUM", "SUPER", "SYMMETRIC", "SYNONYM",
+    "SYSTEM", "TABLE", "TABLESAMPLE", "TEMP", "TEMPORARY", "TERMINATED", "TEXT", "THAN", "THEN",
+    "THROUGHPUT", "TIME", "TIMESTAMP", "TIMEZONE", "TINYINT", "TO", "TOKEN", "TOTAL", "TOUCH",
+    "TRAILING", "TRANSACTION", "TRANSFORM", "TRANSLATE", "TRANSLATION", "TREAT", "TRIGGER", "TRIM",
+    "TRUE", "TRUNCATE", "TTL", "TUPLE", "TYPE", "UNDER", "UNDO", "UNION", "UNIQUE", "UNIT",
+    "UNKNOWN", "UNLOGGED", "UNNEST", "UNPROCESSED", "UNSIGNED", "UNTIL", "UPDATE", "UPPER", "URL",
+    "USAGE", "USE", "USER", "USERS", "USING", "UUID", "VACUUM", "VALUE", "VALUED", "VALUES",
+    "VARCHAR", "VARIABLE", "VARIANCE", "VARINT", "VARYING", "VIEW", "VIEWS", "VIRTUAL", "VOID",
+    "WAIT", "WHEN", "WHENEVER", "WHERE", "WHILE", "WINDOW", "WITH", "WITHIN", "WITHOUT", "WORK",
+    "WRAPPED", "WRITE", "YEAR", "ZONE",
+];
+
+/// Returns `true` if `name` is a DynamoDB reserved word (case-insensitive).
+pub fn is_reserved_word(name: &str) -> bool {
+    RESERVED_WORDS
+        .binary_search(&name.to_ascii_uppercase().as_str())
+        .is_ok()
+}
+
+// A document path such as `address.lines[0]`, or the reason it is invalid
+#[derive(Debug, Clone, PartialEq)]
+struct Path(std::result::Result<Vec<Segment>, String>);
+
+#[derive(Debug, Clone, PartialEq)]
+enum Segment {
+    Name(String),
+    Index(usize),
+}
+
+impl Path {
+    fn parse(path: &str) -> Self {
+        let mut segments = Vec::new();
+        for part in path.split('.') {
+            let (name, mut rest) = part.split_at(part.find('[').unwrap_or(part.len()));
+            if name.is_empty() {
+                return Self(Err(format!("empty attribute name in path '{}'", path)));
+            }
+            segments.push(Segment::Name(name.to_string()));
+            while !rest.is_empty() {
+                let index = rest
+                    .strip_prefix('[')
+                    .and_then(|r| r.split_once(']'))
+                    .and_then(|(index, r)| Some((index.parse().ok()?, r)));
+                let Some((index, r)) = index else {
+                    return Self(Err(format!("invalid list index in path '{}'", path)));
+                };
+                segments.push(Segment::Index(index));
+                rest = r;
+            }
+        }
+        Self(Ok(segments))
+    }
+}
+
+// A converted value, or the reason the conversion failed
+type Value = std::result::Result<AttributeValue, String>;
+
+fn value<V: Serialize>(value: V) -> Value {
+    serde_dynamo::to_attribute_value(value).map_err(|e| e.to_string())
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Operand {
+    Path(Path),
+    Size(Path),
+    Value(Value),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Cond {
+    Compare(Operand, &'static str, Operand),
+    Between(Operand, Operand, Operand),
+    In(Operand, Vec<Operand>),
+    Function(&'static str, Vec<Operand>),
+    And(Box<Cond>, Box<Cond>),
+    Or(Box<Cond>, Box<Cond>),
+    Not(Box<Cond>),
+}
+
+/// A condition or filter expression, built from [`attr`].
+///
+/// Combine conditions with [`Condition::and`], [`Condition::or`] and `!`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Condition(Cond);
+
+impl Condition {
+    /// Both conditions hold.
+    pub fn and(self, other: Condition) -> Condition {
+        Condition(Cond::And(Box::new(self.0), Box::new(other.0)))
+    }
+
+    /// At least one of the conditions holds.
+    pub fn or(self, other: Condition) -> Condition {
+        Condition(Cond::Or(Box::new(self.0), Box::new(other.0)))
+    }
+
+    /// Renders the condition on its own.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` ([`DynamoToolsError::InvalidExpression`]) for an invalid path
+    /// or a value that cannot be converted.
+    pub fn build(&self) -> Result<Expression> {
+        let mut exprs = Expressions::new();
+        let expression = exprs.condition(self)?;
+        Ok(exprs.finish(expression))
+    }
+}
+
+impl Not for Condition {
+    type Output = Condition;
+
+    fn not(self) -> Condition {
+        Condition(Cond::Not(Box::new(self.0)))
+    }
+}
+
+/// Refers to an attribute by its document path, e.g. `status` or `address.lines[0]`.
+///
+/// Dots separate map keys and `[n]` selects list elements.
+pub fn attr(path: &str) -> Attr {
+    Attr(Path::parse(path))
+}
+
+/// An attribute in a [`Condition`]. Created by [`attr`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Attr(Path);
+
+macro_rules! comparisons {
+    ($operand:expr) => {
+        /// The value equals `value`.
+        pub fn eq<V: Serialize>(self, value: V) -> Condition {
+            self.compare("=", value)
+        }
+
+        /// The value differs from `value`.
+        pub fn ne<V: Serialize>(self, value: V) -> Condition {
+            self.compare("<>", value)
+        }
+
+        /// The value is less than `value`.
+        pub fn lt<V: Serialize>(self, value: V) -> Condition {
+            self.compare("<", value)
+        }
+
+        /// The value is less than or equal to `value`.
+        pub fn le<V: Serialize>(self, value: V) -> Condition {
+            self.compare("<=", value)
+        }
+
+        /// The value is greater than `value`.
+        pub fn gt<V: Serialize>(self, value: V) -> Condition {
+            self.compare(">", value)
+        }
+
+        /// The value is greater than or equal to `value`.
+        pub fn ge<V: Serialize>(self, value: V) -> Condition {
+            self.compare(">=", value)
+        }
+
+        /// The value is between `low` and `high` (inclusive).
+        pub fn between<V: Serialize>(self, low: V, high: V) -> Condition {
+            let operand = $operand(self.0);
+            Condition(Cond::Between(
+                operand,
+                Operand::Value(value(low)),
+                Operand::Value(value(high)),
+            ))
+        }
+
+        fn compare<V: Serialize>(self, op: &'static str, v: V) -> Condition {
+            Condition(Cond::Compare(
+                $operand(self.0),
+                op,
+                Operand::Value(value(v)),
+            ))
+        }
+    };
+}
+
+impl Attr {
+    comparisons!(Operand::Path);
+
+    /// The value is one of `values`.
+    pub fn is_in<V: Serialize>(self, values: impl IntoIterator<Item = V>) -> Condition {
+        let values = values
+            .into_iter()
+            .map(|v| Operand::Value(value(v)))
+            .collect();
+        Condition(Cond::In(Operand::Path(self.0), values))
+    }
+
+    /// The attribute exists.
+    pub fn exists(self) -> Condition {
+        self.function("attribute_exists", vec![])
+    }
+
+    /// The attribute does not exist.
+    pub fn not_exists(self) -> Condition {
+        self.function("attribute_not_exists", vec![])
+    }
+
+    /// The attribute is of the given DynamoDB type (`S`, `N`, `B`, `SS`, `NS`, `BS`,
+    /// `BOOL`, `NULL`, `L` or `M`).
+    pub fn has_type(self, attr_type: &str) -> Condition {
+        self.function("attribute_type", vec![Operand::Value(value(attr_type))])
+    }
+
+    /// The string or binary value starts with `prefix`.
+    pub fn begins_with<V: Serialize>(self, prefix: V) -> Condition {
+        self.function("begins_with", vec![Operand::Value(value(prefix))])
+    }
+
+    /// The string value contains `operand` as a substring, or the set or list
+    /// value contains it as an element.
+    pub fn contains<V: Serialize>(self, operand: V) -> Condition {
+        self.function("contains", vec![Operand::Value(value(operand))])
+    }
+
+    /// The size of the value (string length, binary length or number of elements).
+    pub fn size(self) -> Size {
+        Size(self.0)
+    }
+
+    fn function(self, name: &'static str, mut args: Vec<Operand>) -> Condition {
+        args.insert(0, Operand::Path(self.0));
+        Condition(Cond::Function(name, args))
+    }
+}
+
+/// The size of an attribute in a [`Condition`]. Created by [`Attr::size`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Size(Path);
+
+impl Size {
+    comparisons!(Operand::Size);
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum SetAction {
+    Value(Value),
+    IfNotExists(Value),
+    Append(Value),
+    Prepend(Value),
+    Plus(Value),
+    Minus(Value),
+}
+
+/// An update expression: `SET`, `REMOVE`, `ADD` and `DELETE` actions.
+///
+/// ```rust,ignore
+/// let update = Update::new()
+///     .set("status", "shipped")
+///     .set_if_not_exists("created_at", now)
+///     .append_to_list("history", vec!["shipped"])
+///     .increment("version", 1)
+///     .remove("draft");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Update {
+    set: Vec<(Path, SetAction)>,
+    remove: Vec<Path>,
+    add: Vec<(Path, Value)>,
+    delete: Vec<(Path, Value)>,
+}
+
+impl Update {
+    /// Creates an empty update.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// `SET path = value`.
+    pub fn set<V: Serialize>(self, path: &str, v: V) -> Self {
+        self.set_action(path, SetAction::Value(value(v)))
+    }
+
+    /// `SET path = if_not_exists(path, value)`: sets the value only if the
+    /// attribute is missing.
+    pub fn set_if_not_exists<V: Serialize>(self, path: &str, v: V) -> Self {
+        self.set_action(path, SetAction::IfNotExists(value(v)))
+    }
+
+    /// `SET path = list_append(path, values)`: appends to a list.
+    pub fn append_to_list<V: Serialize>(self, path: &str, values: V) -> Self {
+        self.set_action(path, SetAction::Append(value(values)))
+    }
+
+    /// `SET path = list_append(values, path)`: prepends to a list.
+    pub fn prepend_to_list<V: Serialize>(self, path: &str, values: V) -> Self {
+        self.set_action(path, SetAction::Prepend(value(values)))
+    }
+
+    /// `SET path = path + by`: increments an existing number.
+    pub fn increment<V: Serialize>(self, path: &str, by: V) -> Self {
+        self.set_action(path, SetAction::Plus(value(by)))
+    }
+
+    /// `SET path = path - by`: decrements an existing number.
+    pub fn decrement<V: Serialize>(self, path: &str, by: V) -> Self {
+        self.set_action(path, SetAction::Minus(value(by)))
+    }
+
+    /// `REMOVE path`.
+    pub fn remove(mut self, path: &str) -> Self {
+        self.remove.push(Path::parse(path));
+        self
+    }
+
+    /// `ADD path value`: adds to a number (creating it as 0 first) or adds
+    /// elements to a set. Sets are written with `serde_dynamo::string_set::StringSet`
+    /// and its number and binary counterparts.
+    pub fn add<V: Serialize>(mut self, path: &str, v: V) -> Self {
+        self.add.push((Path::parse(path), value(v)));
+        self
+    }
+
+    /// `DELETE path value`: removes elements from a set.
+    pub fn delete<V: Serialize>(mut self, path: &str, v: V) -> Self {
+        self.delete.push((Path::parse(path), value(v)));
+        self
+    }
+
+    /// Renders the update on its own.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` ([`DynamoToolsError::InvalidExpression`]) if the update is empty,
+    /// for an invalid path or for a value that cannot be converted.
+    pub fn build(&self) -> Result<Expression> {
+        let mut exprs = Expressions::new();
+        let expression = exprs.update(self)?;
+        Ok(exprs.finish(expression))
+    }
+
+    fn set_action(mut self, path: &str, action: SetAction) -> Self {
+        self.set.push((Path::parse(path), action));
+        self
+    }
+}
+
+/// A rendered expression with its attribute name and value placeholders.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Expression {
+    /// The expression string.
+    pub expression: String,
+    /// Expression attribute names (`#n0` → name).
+    pub names: HashMap<String, String>,
+    /// Expression attribute values (`:v0` → value).
+    pub values: HashMap<String, AttributeValue>,
+}
+
+/// Renders several expressions of one request with shared placeholders, e.g. the
+/// update and condition expressions of an `UpdateItem`.
+///
+/// Attribute names are written as is unless they are reserved words or contain
+/// characters other than letters and digits; those get `#n<i>` placeholders.
+/// Every value gets a `:v<i>` placeholder.
+///
+/// ```rust,ignore
+/// let mut exprs = Expressions::new();
+/// client
+///     .update_item()
+///     .update_expression(exprs.update(&update)?)
+///     .condition_expression(exprs.condition(&attr("status").eq("open"))?)
+///     .set_expression_attribute_names(exprs.names())
+///     .set_expression_attribute_values(exprs.values())
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Expressions {
+    names: HashMap<String, String>,
+    // Reverse lookup, so a name used twice gets one placeholder
+    placeholders: HashMap<String, String>,
+    values: HashMap<String, AttributeValue>,
+}
+
+impl Expressions {
+    /// Creates an empty set of placeholders.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Renders a condition (or filter) expression.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` ([`DynamoToolsError::InvalidExpression`]) for an invalid path
+    /// or a value that cannot be converted.
+    pub fn condition(&mut self, condition: &Condition) -> Result<String> {
+        self.cond(&condition.0)
+    }
+
+    /// Renders an update expression.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` ([`DynamoToolsError::InvalidExpression`]) if the update is empty,
+    /// for an invalid path or for a value that cannot be converted.
+    pub fn update(&mut self, update: &Update) -> Result<String> {
+        let mut clauses = Vec::new();
+        if !update.set.is_empty() {
+            let mut actions = Vec::new();
+            for (path, action) in &update.set {
+                let path = self.path(path)?;
+                let action = match action {
+                    SetAction::Value(v) => self.value(v)?,
+                    SetAction::IfNotExists(v) => {
+                        format!("if_not_exists({}, {})", path, self.value(v)?)
+                    }
+                    SetAction::Append(v) => format!("list_append({}, {})", path, self.value(v)?),
+                    SetAction::Prepend(v) => format!("list_append({}, {})", self.value(v)?, path),
+                    SetAction::Plus(v) => format!("{} + {}", path, self.value(v)?),
+                    SetAction::Minus(v) => format!("{} - {}", path, self.value(v)?),
+                };
+                actions.push(format!("{} = {}", path, action));
+            }
+            clauses.push(format!("SET {}", actions.join(", ")));
+        }
+        if !update.remove.is_empty() {
+            let paths = update
+                .remove
+                .iter()
+                .map(|path| self.path(path))
+                .collect::<Result<Vec<_>>>()?;
+            clauses.push(format!("REMOVE {}", paths.join(", ")));
+        }
+        for (keyword, actions) in [("ADD", &update.add), ("DELETE", &update.delete)] {
+            if actions.is_empty() {
+                continue;
+            }
+            let actions = actions
+                .iter()
+                .map(|(path, v)| Ok(format!("{} {}", self.path(path)?, self.value(v)?)))
+                .collect::<Result<Vec<_>>>()?;
+            clauses.push(format!("{} {}", keyword, actions.join(", ")));
+        }
+        if clauses.is_empty() {
+            return Err(DynamoToolsError::InvalidExpression(
+                "the update has no actions".to_string(),
+            ));
+        }
+        Ok(clauses.join(" "))
+    }
+
+    /// Renders a projection expression selecting `paths`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` ([`DynamoToolsError::InvalidExpression`]) for an invalid path.
+    pub fn projection<S: AsRef<str>>(
+        &mut self,
+        paths: impl IntoIterator<Item = S>,
+    ) -> Result<String> {
+        let paths = paths
+            .into_iter()
+            .map(|path| self.path(&Path::parse(path.as_ref())))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(paths.join(", "))
+    }
+
+    /// Returns the expression attribute names, or `None` if there are none
+    /// (DynamoDB rejects empty maps).
+    pub fn names(&self) -> Option<HashMap<String, String>> {
+        (!self.names.is_empty()).then(|| self.names.clone())
+    }
+
+    /// Returns the expression attribute values, or `None` if there are none.
+    pub fn values(&self) -> Option<HashMap<String, AttributeValue>> {
+        (!self.values.is_empty()).then(|| self.values.clone())
+    }
+
+    fn finish(self, expression: String) -> Expression {
+        Expression {
+            expression,
+            names: self.names,
+            values: self.values,
+        }
+    }
+
+    fn cond(&mut self, cond: &Cond) -> Result<String> {
+        Ok(match cond {
+            Cond::Compare(left, op, right) => {
+                format!("{} {} {}", self.operand(left)?, op, self.operand(right)?)
+            }
+            Cond::Between(operand, low, high) => format!(
+                "{} BETWEEN {} AND {}",
+                self.operand(operand)?,
+                self.operand(low)?,
+                self.operand(high)?
+            ),
+            Cond::In(operand, values) => {
+                if values.is_empty() || values.len() > 100 {
+                    return Err(DynamoToolsError::InvalidExpression(
+                        "IN takes between 1 and 100 values".to_string(),
+                    ));
+                }
+                let operand = self.operand(operand)?;
+                let values = values
+                    .iter()
+                    .map(|v| self.operand(v))
+                    .collect::<Result<Vec<_>>>()?;
+                format!("{} IN ({})", operand, values.join(", "))
+            }
+            Cond::Function(name, args) => {
+                let args = args
+                    .iter()
+                    .map(|arg| self.operand(arg))
+                    .collect::<Result<Vec<_>>>()?;
+                format!("{}({})", name, args.join(", "))
+            }
+            // NOT binds tighter than AND, which binds tighter than OR
+            Cond::And(left, right) => format!(
+                "{} AND {}",
+                self.nested(left, matches!(**left, Cond::Or(..)))?,
+                self.nested(right, matches!(**right, Cond::Or(..)))?
+            ),
+            Cond::Or(left, right) => format!("{} OR {}", self.cond(left)?, self.cond(right)?),
+            Cond::Not(inner) => format!(
+                "NOT {}",
+                self.nested(inner, matches!(**inner, Cond::And(..) | Cond::Or(..)))?
+            ),
+        })
+    }
+
+    fn nested(&mut self, cond: &Cond, parenthesize: bool) -> Result<String> {
+        let rendered = self.cond(cond)?;
+        Ok(if parenthesize {
+            format!("({})", rendered)
+        } else {
+            rendered
+        })
+    }
+
+    fn operand(&mut self, operand: &Operand) -> Result<String> {
+        match operand {
+            Operand::Path(path) => self.path(path),
+            Operand::Size(path) => Ok(format!("size({})", self.path(path)?)),
+            Operand::Value(v) => self.value(v),
+        }
+    }
+
+    fn path(&mut self, path: &Path) -> Result<String> {
+        let segments = path
+            .0
+            .as_ref()
+            .map_err(|e| DynamoToolsError::InvalidExpression(e.clone()))?;
+        let mut rendered = String::new();
+        for segment in segments {
+            match segment {
+                Segment::Name(name) => {
+                    if !rendered.is_empty() {
+                        rendered.push('.');
+                    }
+                    rendered.push_str(&self.name(name));
+                }
+                Segment::Index(index) => rendered.push_str(&format!("[{}]", index)),
+            }
+        }
+        Ok(rendered)
+    }
+
+    fn name(&mut self, name: &str) -> String {
+        let plain = name.starts_with(|c: char| c.is_ascii_alphabetic())
+            && name.chars().all(|c| c.is_ascii_alphanumeric());
+        if plain && !is_reserved_word(name) {
+            return name.to_string();
+        }
+        if let Some(placeholder) = self.placeholders.get(name) {
+            return placeholder.clone();
+        }
+        let placeholder = format!("#n{}", self.names.len());
+        self.names.insert(placeholder.clone(), name.to_string());
+        self.placeholders
+            .insert(name.to_string(), placeholder.clone());
+        placeholder
+    }
+
+    fn value(&mut self, v: &Value) -> Result<String> {
+        let v = v
+            .as_ref()
+            .map_err(|e| DynamoToolsError::InvalidExpression(e.clone()))?;
+        let placeholder = format!(":v{}", self.values.len());
+        self.values.insert(placeholder.clone(), v.clone());
+        Ok(placeholder)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn s(value: &str) -> AttributeValue {
+        AttributeValue::S(value.to_string())
+    }
+
+    #[test]
+    fn reserved_words_should_be_sorted_and_escaped() {
+        assert!(RESERVED_WORDS.windows(2).all(|w| w[0] < w[1]));
+        assert!(is_reserved_word("status"));
+        assert!(is_reserved_word("Name"));
+        assert!(!is_reserved_word("email"));
+
+        let expr = attr("status").eq("open").build().unwrap();
+        assert_eq!(expr.expression, "#n0 = :v0");
+        assert_eq!(expr.names["#n0"], "status");
+        assert_eq!(expr.values[":v0"], s("open"));
+    }
+
+    #[test]
+    fn conditions_should_render_with_precedence() {
+        let condition = attr("pk")
+            .exists()
+            .and(attr("total").gt(10).or(attr("tags").contains("vip")))
+            .and(!attr("address.street[0]").begins_with("PO"))
+            .or(attr("user-id").size().between(1, 5))
+            .and(attr("kind").is_in(["a", "b"]));
+        let expr = condition.build().unwrap();
+        assert_eq!(
+            expr.expression,
+            "(attribute_exists(pk) AND (#n0 > :v0 OR contains(tags, :v1)) AND NOT begins_with(address.street[0], :v2) OR size(#n1) BETWEEN :v3 AND :v4) AND kind IN (:v5, :v6)"
+        );
+        assert_eq!(expr.names["#n0"], "total");
+        assert_eq!(expr.names["#n1"], "user-id");
+        assert_eq!(expr.values[":v0"], AttributeValue::N("10".to_string()));
+        assert_eq!(expr.values.len(), 7);
+    }
+
+    #[test]
+    fn update_should_render_all_clauses() {
+        let update = Update::new()
+            .set("name", "Bob")
+            .set_if_not_exists("createdAt", 1)
+            .append_to_list("history", vec!["x"])
+            .increment("version", 1)
+            .remove("draft")
+            .remove("data.tmp")
+            .add("visits", 1)
+            .delete("tags", serde_dynamo::string_set::StringSet(vec!["old"]));
+        let expr = update.build().unwrap();
+        assert_eq!(
+            expr.expression,
+            "SET #n0 = :v0, createdAt = if_not_exists(createdAt, :v1), history = list_append(history, :v2), version = version + :v3 REMOVE draft, #n1.tmp ADD visits :v4 DELETE tags :v5"
+        );
+        assert_eq!(expr.names["#n1"], "data");
+        assert_eq!(
+            expr.values[":v5"],
+            AttributeValue::Ss(vec!["old".to_string()])
+        );
+    }
+
+    #[test]
+    fn expressions_should_share_placeholders() {
+        let mut exprs = Expressions::new();
+        let update = exprs.update(&Update::new().set("status", "done")).unwrap();
+        let condition = exprs.condition(&attr("status").eq("open")).unwrap();
+        let projection = exprs.projection(["status", "pk"]).unwrap();
+        assert_eq!(update, "SET #n0 = :v0");
+        assert_eq!(condition, "#n0 = :v1");
+        assert_eq!(projection, "#n0, pk");
+        assert_eq!(exprs.names().unwrap().len(), 1);
+        assert_eq!(exprs.values().unwrap().len(), 2);
+        assert_eq!(Expressions::new().names(), None);
+    }
+
+    #[test]
+    fn invalid_expressions_should_be_rejected() {
+        for err in [
+            Update::new().build(),
+            attr("a..b").exists().build(),
+            attr("a[x]").exists().build(),
+            attr("a").is_in(Vec::<String>::new()).build(),
+        ] {
+            assert!(matches!(err, Err(DynamoToolsError::InvalidExpression(_))));
+        }
+    }
+}
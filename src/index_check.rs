@@ -0,0 +1,183 @@
+use crate::TableInfo;
+use crate::error::{DynamoToolsError, Result};
+use crate::item::{self, Item};
+use aws_sdk_dynamodb::Client;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+/// The result of checking the items of a table against one of its indexes.
+///
+/// Produced by [`crate::DynamodbConnector::check_indexes`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IndexReport {
+    /// The name of the GSI or LSI.
+    pub index_name: String,
+    /// Number of items carrying all key attributes of the index.
+    pub items_checked: usize,
+    /// Primary keys (as DynamoDB JSON) of items that could not be found by
+    /// querying the index with their index key.
+    pub unqueryable: Vec<String>,
+    /// Non-key attributes present on items but not returned through the index,
+    /// with the number of items affected.
+    pub missing_attributes: BTreeMap<String, usize>,
+}
+
+impl IndexReport {
+    /// Returns `true` if every checked item was found through the index with
+    /// all of its attributes.
+    pub fn is_complete(&self) -> bool {
+        self.unqueryable.is_empty() && self.missing_attributes.is_empty()
+    }
+
+    // Records the outcome for `item`, given what querying the index returned for it
+    fn record(&mut self, item: &Item, key_id: String, found: Option<&Item>) {
+        self.items_checked += 1;
+        let Some(found) = found else {
+            self.unqueryable.push(key_id);
+            return;
+        };
+        for name in item.keys().filter(|name| !found.contains_key(*name)) {
+            *self.missing_attributes.entry(name.clone()).or_default() += 1;
+        }
+    }
+}
+
+impl fmt::Display for IndexReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "index '{}': {} items checked",
+            self.index_name, self.items_checked
+        )?;
+        if !self.unqueryable.is_empty() {
+            write!(f, ", not queryable: {}", self.unqueryable.join(", "))?;
+        }
+        if !self.missing_attributes.is_empty() {
+            let missing: Vec<String> = self
+                .missing_attributes
+                .iter()
+                .map(|(name, count)| format!("{} ({} items)", name, count))
+                .collect();
+            write!(f, ", not projected: {}", missing.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+/// Checks `items` of `table_name` against every GSI and LSI of `info`.
+pub(crate) async fn check_indexes(
+    client: &Client,
+    table_name: &str,
+    info: &TableInfo,
+    items: &[Item],
+) -> Result<Vec<IndexReport>> {
+    let indexes = info
+        .gsis
+        .iter()
+        .map(|gsi| (&gsi.name, &gsi.pk.name, gsi.sk.as_ref().map(|sk| &sk.name)))
+        .chain(
+            info.lsis
+                .iter()
+                .map(|lsi| (&lsi.name, &lsi.pk.name, Some(&lsi.sk.name))),
+        );
+
+    let key_names = info.key_names();
+    let mut reports = Vec::new();
+    for (index_name, pk, sk) in indexes {
+        let mut report = IndexReport {
+            index_name: index_name.clone(),
+            ..Default::default()
+        };
+        for item in items {
+            // Items without all index key attributes are not in the index at all
+            let Some(pk_value) = item.get(pk) else {
+                continue;
+            };
+            let sk_value = match sk {
+                Some(sk) => match item.get(sk) {
+                    Some(value) => Some((sk, value)),
+                    None => continue,
+                },
+                None => None,
+            };
+            let mut request = client
+                .query()
+                .table_name(table_name)
+                .index_name(index_name)
+                .expression_attribute_names("#pk", pk)
+                .expression_attribute_values(":pk", pk_value.clone());
+            request = match sk_value {
+                Some((sk, value)) => request
+                    .key_condition_expression("#pk = :pk AND #sk = :sk")
+                    .expression_attribute_names("#sk", sk)
+                    .expression_attribute_values(":sk", value.clone()),
+                None => request.key_condition_expression("#pk = :pk"),
+            };
+
+            let key_id = item::key_id(item, &key_names)?;
+            let mut found = None;
+            let mut start_key: Option<HashMap<_, _>> = None;
+            loop {
+                let resp = request
+                    .clone()
+                    .set_exclusive_start_key(start_key.take())
+                    .send()
+                    .await
+                    .map_err(|e| DynamoToolsError::Query(table_name.to_string(), e))?;
+                for candidate in resp.items() {
+                    if item::key_id(candidate, &key_names)? == key_id {
+                        found = Some(candidate.clone());
+                    }
+                }
+                match resp.last_evaluated_key {
+                    Some(key) if found.is_none() => start_key = Some(key),
+                    _ => break,
+                }
+            }
+            report.record(item, key_id, found.as_ref());
+        }
+        reports.push(report);
+    }
+    Ok(reports)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aws_sdk_dynamodb::types::AttributeValue;
+
+    fn item(attrs: &[&str]) -> Item {
+        attrs
+            .iter()
+            .map(|name| (name.to_string(), AttributeValue::S(name.to_string())))
+            .collect()
+    }
+
+    #[test]
+    fn report_should_collect_unqueryable_items_and_missing_attributes() {
+        let mut report = IndexReport {
+            index_name: "gsi1".to_string(),
+            ..Default::default()
+        };
+        let full = item(&["pk", "gsi1pk", "name", "email"]);
+        report.record(
+            &full,
+            "a".to_string(),
+            Some(&item(&["pk", "gsi1pk", "name"])),
+        );
+        report.record(&full, "b".to_string(), Some(&item(&["pk", "gsi1pk"])));
+        report.record(&full, "c".to_string(), None);
+
+        assert_eq!(report.items_checked, 3);
+        assert_eq!(report.unqueryable, vec!["c".to_string()]);
+        assert_eq!(
+            report.missing_attributes,
+            BTreeMap::from([("email".to_string(), 2), ("name".to_string(), 1)])
+        );
+        assert!(!report.is_complete());
+        assert_eq!(
+            report.to_string(),
+            "index 'gsi1': 3 items checked, not queryable: c, not projected: email (2 items), name (1 items)"
+        );
+    }
+}
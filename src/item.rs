@@ -0,0 +1,376 @@
+use crate::error::{DynamoToolsError, Result};
+use aws_sdk_dynamodb::primitives::Blob;
+use aws_sdk_dynamodb::types::AttributeValue;
+use base64::{Engine, engine::general_purpose::STANDARD};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+#[cfg(feature = "connector")]
+use std::io::Write;
+
+/// A single DynamoDB item in its SDK representation.
+pub type Item = HashMap<String, AttributeValue>;
+
+/// File formats used to export items and to load them back as seed data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DataFormat {
+    /// A JSON array of plain JSON objects (the classic `seed_data_file` format).
+    Json,
+    /// One plain JSON object per line.
+    NdJson,
+    /// One `{"Item": {...}}` object per line with typed attribute values
+    /// (e.g. `{"S": "..."}`), as produced by DynamoDB's export to S3. Lossless.
+    DynamoDbJson,
+}
+
+impl DataFormat {
+    /// Detects the format of `content`.
+    ///
+    /// A leading `[` means [`DataFormat::Json`]. Otherwise the content is treated as
+    /// line-delimited, and it is [`DataFormat::DynamoDbJson`] if the first object
+    /// has `Item` as its only key, [`DataFormat::NdJson`] if not.
+    pub fn detect(content: &str) -> Self {
+        let content = content.trim_start();
+        if content.starts_with('[') {
+            return DataFormat::Json;
+        }
+        let first = content.lines().next().unwrap_or_default();
+        match serde_json::from_str::<Value>(first) {
+            Ok(Value::Object(obj)) if is_dynamodb_json_line(&obj) => DataFormat::DynamoDbJson,
+            _ => DataFormat::NdJson,
+        }
+    }
+}
+
+fn is_dynamodb_json_line(obj: &Map<String, Value>) -> bool {
+    obj.len() == 1 && obj.get("Item").is_some_and(Value::is_object)
+}
+
+/// Parses `content` into items, detecting the format with [`DataFormat::detect`].
+///
+/// `source` is only used in error messages.
+pub(crate) fn parse_items(content: &str, source: &str) -> Result<Vec<Item>> {
+    let json_err = |e| DynamoToolsError::SeedJsonParse(source.to_string(), e);
+    match DataFormat::detect(content) {
+        DataFormat::Json => {
+            let values: Vec<Value> = serde_json::from_str(content).map_err(json_err)?;
+            values.into_iter().map(plain_json_to_item).collect()
+        }
+        format => content
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| {
+                let value: Value = serde_json::from_str(line).map_err(json_err)?;
+                if format == DataFormat::DynamoDbJson {
+                    match value {
+                        Value::Object(mut obj) if is_dynamodb_json_line(&obj) => {
+                            item_from_dynamodb_json(obj.remove("Item").unwrap_or_default())
+                        }
+                        other => Err(DynamoToolsError::InvalidDynamoDbJson(format!(
+                            "expected an {{\"Item\": ...}} object, got {}",
+                            other
+                        ))),
+                    }
+                } else {
+                    plain_json_to_item(value)
+                }
+            })
+            .collect(),
+    }
+}
+
+/// Writes `items` to `writer` in the given `format`.
+#[cfg(feature = "connector")]
+pub(crate) fn write_items<W: Write>(
+    mut writer: W,
+    items: &[Item],
+    format: DataFormat,
+) -> Result<()> {
+    let io_err = |e: std::io::Error| DynamoToolsError::Internal(format!("Write failed: {}", e));
+    match format {
+        DataFormat::Json => {
+            let values = items
+                .iter()
+                .map(item_to_plain_json)
+                .collect::<Result<Vec<_>>>()?;
+            serde_json::to_writer_pretty(&mut writer, &values)
+                .map_err(|e| DynamoToolsError::Internal(format!("Write failed: {}", e)))?;
+            writeln!(writer).map_err(io_err)?;
+        }
+        DataFormat::NdJson | DataFormat::DynamoDbJson => {
+            for item in items {
+                let value = if format == DataFormat::NdJson {
+                    item_to_plain_json(item)?
+                } else {
+                    Value::Object(Map::from_iter([(
+                        "Item".to_string(),
+                        item_to_dynamodb_json(item)?,
+                    )]))
+                };
+                writeln!(writer, "{}", value).map_err(io_err)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn plain_json_to_item(value: Value) -> Result<Item> {
+    Ok(serde_dynamo::to_item(value)?)
+}
+
+#[cfg(feature = "connector")]
+fn item_to_plain_json(item: &Item) -> Result<Value> {
+    Ok(serde_dynamo::from_item(item.clone())?)
+}
+
+/// Converts an item to its DynamoDB JSON form (`{"name": {"S": "..."}}`).
+///
+/// # Errors
+///
+/// Returns `Err` ([`DynamoToolsError::InvalidDynamoDbJson`]) if the item holds an
+/// attribute value type this crate does not know (added to the SDK later on).
+pub fn item_to_dynamodb_json(item: &Item) -> Result<Value> {
+    item.iter()
+        .map(|(k, v)| Ok((k.clone(), attribute_to_dynamodb_json(v)?)))
+        .collect::<Result<Map<_, _>>>()
+        .map(Value::Object)
+}
+
+/// Converts a DynamoDB JSON object (`{"name": {"S": "..."}}`) to an item.
+///
+/// # Errors
+///
+/// Returns `Err` ([`DynamoToolsError::InvalidDynamoDbJson`]) if `value` is not
+/// a well-formed DynamoDB JSON object.
+pub fn item_from_dynamodb_json(value: Value) -> Result<Item> {
+    match value {
+        Value::Object(obj) => obj
+            .into_iter()
+            .map(|(k, v)| Ok((k, attribute_from_dynamodb_json(v)?)))
+            .collect(),
+        other => Err(DynamoToolsError::InvalidDynamoDbJson(format!(
+            "expected an object, got {}",
+            other
+        ))),
+    }
+}
+
+/// Extracts the primary key attributes of `item`.
+pub(crate) fn extract_key(item: &Item, key_names: &[String]) -> Result<Item> {
+    key_names
+        .iter()
+        .map(|name| {
+            item.get(name)
+                .map(|v| (name.clone(), v.clone()))
+                .ok_or_else(|| {
+                    DynamoToolsError::MissingField(format!("Key attribute '{}' in item", name))
+                })
+        })
+        .collect()
+}
+
+/// Returns a canonical string for the primary key of `item`, usable as a map key.
+///
+/// The key attributes are written in the order of `key_names`, e.g.
+/// `{"pk":{"S":"user_1"},"sk":{"N":"2"}}`, so equal keys always give equal ids.
+pub(crate) fn key_id(item: &Item, key_names: &[String]) -> Result<String> {
+    let key = extract_key(item, key_names)?;
+    let attributes = key_names
+        .iter()
+        .map(|name| {
+            Ok(format!(
+                "{}:{}",
+                serde_json::Value::from(name.as_str()),
+                attribute_to_dynamodb_json(&key[name])?
+            ))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    Ok(format!("{{{}}}", attributes.join(",")))
+}
+
+pub(crate) fn attribute_to_dynamodb_json(value: &AttributeValue) -> Result<Value> {
+    let (tag, inner) = match value {
+        AttributeValue::S(s) => ("S", Value::from(s.as_str())),
+        AttributeValue::N(n) => ("N", Value::from(n.as_str())),
+        AttributeValue::B(b) => ("B", Value::from(STANDARD.encode(b.as_ref()))),
+        AttributeValue::Bool(b) => ("BOOL", Value::from(*b)),
+        AttributeValue::Null(_) => ("NULL", Value::from(true)),
+        AttributeValue::Ss(ss) => ("SS", Value::from(ss.clone())),
+        AttributeValue::Ns(ns) => ("NS", Value::from(ns.clone())),
+        AttributeValue::Bs(bs) => (
+            "BS",
+            bs.iter()
+                .map(|b| Value::from(STANDARD.encode(b.as_ref())))
+                .collect(),
+        ),
+        AttributeValue::L(l) => (
+            "L",
+            l.iter()
+                .map(attribute_to_dynamodb_json)
+                .collect::<Result<Value>>()?,
+        ),
+        AttributeValue::M(m) => ("M", item_to_dynamodb_json(m)?),
+        // Variants added to the SDK later on have no DynamoDB JSON form we know of
+        other => {
+            return Err(DynamoToolsError::InvalidDynamoDbJson(format!(
+                "unsupported attribute value {:?}",
+                other
+            )));
+        }
+    };
+    Ok(Value::Object(Map::from_iter([(tag.to_string(), inner)])))
+}
+
+fn attribute_from_dynamodb_json(value: Value) -> Result<AttributeValue> {
+    let invalid = |v: &dyn std::fmt::Display| {
+        DynamoToolsError::InvalidDynamoDbJson(format!("invalid attribute value {}", v))
+    };
+    let Value::Object(obj) = value else {
+        return Err(invalid(&value));
+    };
+    if obj.len() != 1 {
+        return Err(invalid(&Value::Object(obj)));
+    }
+    let (tag, inner) = obj.into_iter().next().unwrap_or_default();
+
+    let string = |v: Value| match v {
+        Value::String(s) => Ok(s),
+        other => Err(invalid(&other)),
+    };
+    let strings = |v: Value| match v {
+        Value::Array(arr) => arr.into_iter().map(string).collect::<Result<Vec<_>>>(),
+        other => Err(invalid(&other)),
+    };
+    let blob = |s: String| STANDARD.decode(&s).map(Blob::new).map_err(|_| invalid(&s));
+
+    Ok(match (tag.as_str(), inner) {
+        ("S", v) => AttributeValue::S(string(v)?),
+        ("N", v) => AttributeValue::N(string(v)?),
+        ("B", v) => AttributeValue::B(blob(string(v)?)?),
+        ("BOOL", Value::Bool(b)) => AttributeValue::Bool(b),
+        ("NULL", Value::Bool(b)) => AttributeValue::Null(b),
+        ("SS", v) => AttributeValue::Ss(strings(v)?),
+        ("NS", v) => AttributeValue::Ns(strings(v)?),
+        ("BS", v) => AttributeValue::Bs(
+            strings(v)?
+                .into_iter()
+                .map(blob)
+                .collect::<Result<Vec<_>>>()?,
+        ),
+        ("L", Value::Array(arr)) => AttributeValue::L(
+            arr.into_iter()
+                .map(attribute_from_dynamodb_json)
+                .collect::<Result<Vec<_>>>()?,
+        ),
+        ("M", v) => AttributeValue::M(item_from_dynamodb_json(v)?),
+        (tag, inner) => {
+            return Err(invalid(&Value::Object(Map::from_iter([(
+                tag.to_string(),
+                inner,
+            )]))));
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn sample_item() -> Item {
+        HashMap::from([
+            ("pk".to_string(), AttributeValue::S("user_1".to_string())),
+            ("age".to_string(), AttributeValue::N("42".to_string())),
+            (
+                "avatar".to_string(),
+                AttributeValue::B(Blob::new(vec![1, 2, 3])),
+            ),
+            ("active".to_string(), AttributeValue::Bool(true)),
+            (
+                "tags".to_string(),
+                AttributeValue::Ss(vec!["a".to_string(), "b".to_string()]),
+            ),
+            (
+                "address".to_string(),
+                AttributeValue::M(HashMap::from([(
+                    "city".to_string(),
+                    AttributeValue::S("Paris".to_string()),
+                )])),
+            ),
+            (
+                "history".to_string(),
+                AttributeValue::L(vec![AttributeValue::Null(true)]),
+            ),
+        ])
+    }
+
+    #[test]
+    fn dynamodb_json_should_round_trip() {
+        let item = sample_item();
+        let value = item_to_dynamodb_json(&item).unwrap();
+        assert_eq!(value["pk"], json!({ "S": "user_1" }));
+        assert_eq!(value["avatar"], json!({ "B": "AQID" }));
+        assert_eq!(item_from_dynamodb_json(value).unwrap(), item);
+    }
+
+    #[test]
+    fn invalid_dynamodb_json_should_be_rejected() {
+        assert!(item_from_dynamodb_json(json!({ "pk": "user_1" })).is_err());
+        assert!(item_from_dynamodb_json(json!({ "pk": { "X": "user_1" } })).is_err());
+    }
+
+    #[test]
+    fn format_should_be_detected() {
+        assert_eq!(DataFormat::detect("  [{\"pk\": \"a\"}]"), DataFormat::Json);
+        assert_eq!(DataFormat::detect("{\"pk\": \"a\"}\n"), DataFormat::NdJson);
+        assert_eq!(
+            DataFormat::detect("{\"Item\": {\"pk\": {\"S\": \"a\"}}}\n"),
+            DataFormat::DynamoDbJson
+        );
+    }
+
+    #[cfg(feature = "connector")]
+    #[test]
+    fn items_should_round_trip_through_every_format() {
+        let items = vec![HashMap::from([
+            ("pk".to_string(), AttributeValue::S("user_1".to_string())),
+            ("age".to_string(), AttributeValue::N("42".to_string())),
+        ])];
+        for format in [
+            DataFormat::Json,
+            DataFormat::NdJson,
+            DataFormat::DynamoDbJson,
+        ] {
+            let mut buf = Vec::new();
+            write_items(&mut buf, &items, format).unwrap();
+            let content = String::from_utf8(buf).unwrap();
+            assert_eq!(DataFormat::detect(&content), format);
+            assert_eq!(parse_items(&content, "test").unwrap(), items);
+        }
+    }
+
+    #[test]
+    fn key_id_should_follow_key_names() {
+        let item = HashMap::from([
+            ("sk".to_string(), AttributeValue::N("2".to_string())),
+            ("name".to_string(), AttributeValue::S("Alice".to_string())),
+            ("pk".to_string(), AttributeValue::S("user_1".to_string())),
+        ]);
+        let key_names = vec!["pk".to_string(), "sk".to_string()];
+        assert_eq!(
+            key_id(&item, &key_names).unwrap(),
+            r#"{"pk":{"S":"user_1"},"sk":{"N":"2"}}"#
+        );
+        let same_key: Item = item
+            .iter()
+            .filter(|(name, _)| *name != "name")
+            .map(|(name, value)| (name.clone(), value.clone()))
+            .collect();
+        assert_eq!(
+            key_id(&same_key, &key_names).unwrap(),
+            key_id(&item, &key_names).unwrap()
+        );
+        assert!(key_id(&item, &["id".to_string()]).is_err());
+    }
+}
@@ -1,12 +1,69 @@
 #![doc = include_str!("../README.md")]
 
+#[cfg(all(feature = "connector", feature = "test_utils"))]
+mod assertions;
+#[cfg(feature = "auto_scaling")]
+pub mod auto_scaling;
+mod cloudformation;
+mod codegen;
 mod config;
 #[cfg(feature = "connector")]
 mod connector;
+#[cfg(feature = "connector")]
+mod copy;
+#[cfg(feature = "connector")]
+mod entity;
 pub mod error;
+mod expression;
+#[cfg(feature = "connector")]
+mod index_check;
+mod item;
+#[cfg(feature = "connector")]
+mod query;
+#[cfg(feature = "connector")]
+mod scan;
+#[cfg(feature = "connector")]
+mod seed;
+#[cfg(feature = "connector")]
+mod snapshot;
+#[cfg(feature = "connector")]
+mod sweep;
+#[cfg(feature = "connector")]
+mod table;
+#[cfg(feature = "terraform")]
+mod terraform;
+mod workbench;
 
+#[cfg(all(feature = "connector", feature = "test_utils"))]
+pub use assertions::{Redactions, UPDATE_SNAPSHOTS_ENV};
+pub use cloudformation::TemplateFormat;
 // Make config structs/enums public for test construction
-pub use config::{AttrType, TableAttr, TableConfig, TableInfo};
+pub use config::{
+    AttrType, AutoScaling, DynamoTable, Encryption, IndexProjection, Replica, ReplicaGsi,
+    ScalingSettings, SeedMode, StreamViewType, TableAttr, TableClass, TableConfig, TableGsi,
+    TableInfo, TableLsi, Throughput, WarmThroughput,
+};
 #[cfg(feature = "connector")]
 pub use connector::DynamodbConnector;
+#[cfg(feature = "connector")]
+pub use copy::{CopyOptions, copy_table};
+#[cfg(feature = "derive")]
+pub use dynamodb_tools_derive::DynamoTable;
+#[cfg(feature = "connector")]
+pub use entity::{ENTITY_TYPE_ATTR, Entities, Entity, EntityKeys};
 pub use error::{DynamoToolsError, Result};
+pub use expression::{
+    Attr, Condition, Expression, Expressions, Size, Update, attr, is_reserved_word,
+};
+#[cfg(feature = "connector")]
+pub use index_check::IndexReport;
+pub use item::{DataFormat, Item, item_from_dynamodb_json, item_to_dynamodb_json};
+#[cfg(feature = "connector")]
+pub use query::QueryBuilder;
+#[cfg(feature = "connector")]
+pub use snapshot::Snapshot;
+#[cfg(feature = "connector")]
+pub use sweep::{SweepTarget, sweep_orphans};
+#[cfg(feature = "connector")]
+pub use table::TableHandle;
+pub use workbench::{WorkbenchFacet, WorkbenchModel, WorkbenchTable};
@@ -0,0 +1,620 @@
+use crate::error::{DynamoToolsError, Result};
+use crate::expression::{Condition, Expressions};
+use crate::item::Item;
+use crate::{AttrType, TableAttr, TableHandle};
+use aws_sdk_dynamodb::operation::query::builders::QueryFluentBuilder;
+use aws_sdk_dynamodb::types::AttributeValue;
+use futures::{Stream, StreamExt, TryStreamExt, future, stream};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use std::collections::HashMap;
+
+// Condition on the sort key of a query
+#[derive(Debug, Clone)]
+enum SkCondition {
+    Eq(AttributeValue),
+    Lt(AttributeValue),
+    Le(AttributeValue),
+    Gt(AttributeValue),
+    Ge(AttributeValue),
+    Between(AttributeValue, AttributeValue),
+    BeginsWith(AttributeValue),
+}
+
+/// Builds a `Query` on a table or one of its indexes, checked against the
+/// configured key schema before it is sent.
+///
+/// Obtained with [`TableHandle::query_builder`]. Key conditions use the
+/// placeholders `#qpk`, `#qsk`, `:qpk`, `:qsk` and `:qsk2`, and projections
+/// `#qp0`, `#qp1`, ..., which filter expressions must not use. Conditions added
+/// with [`QueryBuilder::filter_condition`] pick their own placeholders (`#n<i>`,
+/// `:v<i>`).
+///
+/// ```rust,ignore
+/// let orders: Vec<Order> = users
+///     .query_builder()
+///     .index("gsi1")
+///     .pk_eq("email#alice")
+///     .sk_begins_with("order#")
+///     .collect()
+///     .await?;
+/// ```
+#[derive(Debug)]
+pub struct QueryBuilder<'a> {
+    handle: &'a TableHandle,
+    index: Option<String>,
+    pk: Option<AttributeValue>,
+    sk: Option<SkCondition>,
+    filter: Option<String>,
+    // Rendered `filter_condition`s, sharing the placeholders of `exprs`
+    conditions: Vec<String>,
+    exprs: Expressions,
+    names: HashMap<String, String>,
+    values: HashMap<String, AttributeValue>,
+    projection: Vec<String>,
+    forward: bool,
+    consistent_read: bool,
+    page_size: Option<i32>,
+    // First conversion error, reported when the query is built
+    error: Option<DynamoToolsError>,
+}
+
+impl<'a> QueryBuilder<'a> {
+    pub(crate) fn new(handle: &'a TableHandle) -> Self {
+        Self {
+            handle,
+            index: None,
+            pk: None,
+            sk: None,
+            filter: None,
+            conditions: Vec::new(),
+            exprs: Expressions::new(),
+            names: HashMap::new(),
+            values: HashMap::new(),
+            projection: Vec::new(),
+            forward: true,
+            consistent_read: false,
+            page_size: None,
+            error: None,
+        }
+    }
+
+    /// Queries the GSI or LSI `name` instead of the table.
+    pub fn index(mut self, name: impl Into<String>) -> Self {
+        self.index = Some(name.into());
+        self
+    }
+
+    /// Selects the items whose partition key equals `value`. Required.
+    pub fn pk_eq<V: Serialize>(mut self, value: V) -> Self {
+        self.pk = self.convert(&value);
+        self
+    }
+
+    /// Keeps the items whose sort key equals `value`.
+    pub fn sk_eq<V: Serialize>(self, value: V) -> Self {
+        self.sk_condition(&value, SkCondition::Eq)
+    }
+
+    /// Keeps the items whose sort key is less than `value`.
+    pub fn sk_lt<V: Serialize>(self, value: V) -> Self {
+        self.sk_condition(&value, SkCondition::Lt)
+    }
+
+    /// Keeps the items whose sort key is less than or equal to `value`.
+    pub fn sk_le<V: Serialize>(self, value: V) -> Self {
+        self.sk_condition(&value, SkCondition::Le)
+    }
+
+    /// Keeps the items whose sort key is greater than `value`.
+    pub fn sk_gt<V: Serialize>(self, value: V) -> Self {
+        self.sk_condition(&value, SkCondition::Gt)
+    }
+
+    /// Keeps the items whose sort key is greater than or equal to `value`.
+    pub fn sk_ge<V: Serialize>(self, value: V) -> Self {
+        self.sk_condition(&value, SkCondition::Ge)
+    }
+
+    /// Keeps the items whose sort key is between `low` and `high` (inclusive).
+    pub fn sk_between<V: Serialize>(mut self, low: V, high: V) -> Self {
+        let (low, high) = (self.convert(&low), self.convert(&high));
+        if let (Some(low), Some(high)) = (low, high) {
+            self.sk = Some(SkCondition::Between(low, high));
+        }
+        self
+    }
+
+    /// Keeps the items whose sort key starts with `prefix` (string or binary keys only).
+    pub fn sk_begins_with<V: Serialize>(self, prefix: V) -> Self {
+        self.sk_condition(&prefix, SkCondition::BeginsWith)
+    }
+
+    /// Adds a filter expression, applied after the key condition. Use
+    /// [`QueryBuilder::name`] and [`QueryBuilder::value`] for its placeholders.
+    pub fn filter(mut self, expression: impl Into<String>) -> Self {
+        self.filter = Some(expression.into());
+        self
+    }
+
+    /// Adds a filter condition built with [`crate::attr`]. Several filters (including
+    /// one from [`QueryBuilder::filter`]) must all hold.
+    ///
+    /// ```rust,ignore
+    /// let open: Vec<Order> = orders
+    ///     .query_builder()
+    ///     .pk_eq("user_1")
+    ///     .filter_condition(&attr("status").eq("open").and(attr("total").gt(10)))
+    ///     .collect()
+    ///     .await?;
+    /// ```
+    pub fn filter_condition(mut self, condition: &Condition) -> Self {
+        match self.exprs.condition(condition) {
+            Ok(rendered) => self.conditions.push(rendered),
+            Err(e) => {
+                self.error.get_or_insert(e);
+            }
+        }
+        self
+    }
+
+    /// Defines the expression attribute name `placeholder` (e.g. `#status`).
+    pub fn name(mut self, placeholder: impl Into<String>, attribute: impl Into<String>) -> Self {
+        self.names.insert(placeholder.into(), attribute.into());
+        self
+    }
+
+    /// Defines the expression attribute value `placeholder` (e.g. `:status`).
+    pub fn value<V: Serialize>(mut self, placeholder: impl Into<String>, value: V) -> Self {
+        if let Some(value) = self.convert(&value) {
+            self.values.insert(placeholder.into(), value);
+        }
+        self
+    }
+
+    /// Returns only the given attributes of each item.
+    pub fn project<I, S>(mut self, attributes: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.projection = attributes.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Returns items in descending sort key order.
+    pub fn descending(mut self) -> Self {
+        self.forward = false;
+        self
+    }
+
+    /// Uses strongly consistent reads (not supported on GSIs).
+    pub fn consistent_read(mut self) -> Self {
+        self.consistent_read = true;
+        self
+    }
+
+    /// Sets the number of items evaluated per request (page).
+    pub fn page_size(mut self, size: i32) -> Self {
+        self.page_size = Some(size);
+        self
+    }
+
+    /// Runs the query, returning its items as a stream that fetches the next page
+    /// when needed.
+    ///
+    /// The stream yields a single error if the query is invalid
+    /// ([`DynamoToolsError::InvalidQuery`]).
+    pub fn stream<T: DeserializeOwned>(self) -> impl Stream<Item = Result<T>> {
+        let table_name = self.handle.name().to_string();
+        let request = match self.request() {
+            Ok(request) => request,
+            Err(e) => return stream::once(future::ready(Err(e))).left_stream(),
+        };
+        let pages = stream::try_unfold(Some(None), move |start: Option<Option<Item>>| {
+            let request = request.clone();
+            let table_name = table_name.clone();
+            async move {
+                let Some(start) = start else {
+                    return Ok::<_, DynamoToolsError>(None);
+                };
+                let resp = request
+                    .set_exclusive_start_key(start)
+                    .send()
+                    .await
+                    .map_err(|e| DynamoToolsError::Query(table_name.clone(), e))?;
+                let next = resp.last_evaluated_key.filter(|key| !key.is_empty());
+                Ok(Some((
+                    (table_name, resp.items.unwrap_or_default()),
+                    next.map(Some),
+                )))
+            }
+        });
+        pages
+            .map_ok(|(table_name, items)| {
+                stream::iter(items.into_iter().map(move |item| {
+                    serde_dynamo::from_item(item)
+                        .map_err(|e| DynamoToolsError::ItemConversion(table_name.clone(), e))
+                }))
+            })
+            .try_flatten()
+            .right_stream()
+    }
+
+    /// Runs the query and collects every item.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if the query is invalid ([`DynamoToolsError::InvalidQuery`]),
+    /// `Query` fails or an item cannot be converted to `T`.
+    pub async fn collect<T: DeserializeOwned>(self) -> Result<Vec<T>> {
+        self.stream().try_collect().await
+    }
+
+    // Validates the query against the key schema and builds the first request
+    fn request(self) -> Result<QueryFluentBuilder> {
+        let handle = self.handle;
+        let invalid = |msg: String| DynamoToolsError::InvalidQuery(handle.name().to_string(), msg);
+        if let Some(e) = self.error {
+            return Err(e);
+        }
+        let (pk_attr, sk_attr) = key_schema(handle, self.index.as_deref()).map_err(invalid)?;
+        let target = match self.index {
+            Some(ref name) => format!("index '{}'", name),
+            None => "the table".to_string(),
+        };
+
+        let Some(pk) = self.pk else {
+            return Err(invalid(
+                "a partition key condition (pk_eq) is required".into(),
+            ));
+        };
+        check_type(&pk, pk_attr).map_err(invalid)?;
+        if self.consistent_read
+            && handle
+                .info()
+                .gsis
+                .iter()
+                .any(|g| Some(&g.name) == self.index.as_ref())
+        {
+            return Err(invalid(format!(
+                "{} is a GSI, which does not support consistent reads",
+                target
+            )));
+        }
+        let reserved = ["#qpk", "#qsk", ":qpk", ":qsk", ":qsk2"];
+        let placeholders = self.names.keys().chain(self.values.keys());
+        if let Some(p) = placeholders
+            .into_iter()
+            .find(|p| reserved.contains(&p.as_str()) || is_projection_placeholder(p))
+        {
+            return Err(invalid(format!(
+                "placeholder '{}' is reserved for key conditions and projections",
+                p
+            )));
+        }
+
+        let condition_names = self.exprs.names().unwrap_or_default();
+        let condition_values = self.exprs.values().unwrap_or_default();
+        let placeholders = self.names.keys().chain(self.values.keys());
+        if let Some(p) = placeholders
+            .into_iter()
+            .find(|p| condition_names.contains_key(*p) || condition_values.contains_key(*p))
+        {
+            return Err(invalid(format!(
+                "placeholder '{}' is already used by a filter condition",
+                p
+            )));
+        }
+        let filters: Vec<String> = self.filter.into_iter().chain(self.conditions).collect();
+        let filter = match filters.len() {
+            0 => None,
+            1 => filters.into_iter().next(),
+            _ => Some(
+                filters
+                    .iter()
+                    .map(|f| format!("({})", f))
+                    .collect::<Vec<_>>()
+                    .join(" AND "),
+            ),
+        };
+
+        let mut names = self.names;
+        let mut values = self.values;
+        names.extend(condition_names);
+        values.extend(condition_values);
+        let mut condition = "#qpk = :qpk".to_string();
+        names.insert("#qpk".into(), pk_attr.name.clone());
+        values.insert(":qpk".into(), pk);
+
+        if let Some(sk) = self.sk {
+            let Some(sk_attr) = sk_attr else {
+                return Err(invalid(format!("{} has no sort key", target)));
+            };
+            let expression = match sk {
+                SkCondition::Eq(v) => ("#qsk = :qsk", v, None),
+                SkCondition::Lt(v) => ("#qsk < :qsk", v, None),
+                SkCondition::Le(v) => ("#qsk <= :qsk", v, None),
+                SkCondition::Gt(v) => ("#qsk > :qsk", v, None),
+                SkCondition::Ge(v) => ("#qsk >= :qsk", v, None),
+                SkCondition::Between(low, high) => ("#qsk BETWEEN :qsk AND :qsk2", low, Some(high)),
+                SkCondition::BeginsWith(v) => {
+                    if sk_attr.attr_type == AttrType::N {
+                        return Err(invalid(format!(
+                            "begins_with needs a string or binary sort key, but '{}' is a number",
+                            sk_attr.name
+                        )));
+                    }
+                    ("begins_with(#qsk, :qsk)", v, None)
+                }
+            };
+            let (expression, value, high) = expression;
+            check_type(&value, sk_attr).map_err(invalid)?;
+            condition.push_str(" AND ");
+            condition.push_str(expression);
+            names.insert("#qsk".into(), sk_attr.name.clone());
+            values.insert(":qsk".into(), value);
+            if let Some(high) = high {
+                check_type(&high, sk_attr).map_err(invalid)?;
+                values.insert(":qsk2".into(), high);
+            }
+        }
+
+        let mut request = handle
+            .client()
+            .query()
+            .table_name(handle.name())
+            .set_index_name(self.index)
+            .key_condition_expression(condition)
+            .set_filter_expression(filter)
+            .scan_index_forward(self.forward)
+            .set_limit(self.page_size);
+        if self.consistent_read {
+            request = request.consistent_read(true);
+        }
+        if !self.projection.is_empty() {
+            let placeholders: Vec<String> = (0..self.projection.len())
+                .map(|i| format!("#qp{}", i))
+                .collect();
+            request = request.projection_expression(placeholders.join(", "));
+            names.extend(placeholders.into_iter().zip(self.projection));
+        }
+        Ok(request
+            .set_expression_attribute_names(Some(names))
+            .set_expression_attribute_values(Some(values)))
+    }
+
+    fn sk_condition<V: Serialize>(
+        mut self,
+        value: &V,
+        condition: fn(AttributeValue) -> SkCondition,
+    ) -> Self {
+        if let Some(value) = self.convert(value) {
+            self.sk = Some(condition(value));
+        }
+        self
+    }
+
+    // Converts `value`, keeping the first error for `request`
+    fn convert<V: Serialize>(&mut self, value: &V) -> Option<AttributeValue> {
+        match serde_dynamo::to_attribute_value(value) {
+            Ok(value) => Some(value),
+            Err(e) => {
+                self.error.get_or_insert(DynamoToolsError::ItemConversion(
+                    self.handle.name().to_string(),
+                    e,
+                ));
+                None
+            }
+        }
+    }
+}
+
+impl TableHandle {
+    /// Starts building a query on the table or one of its indexes.
+    pub fn query_builder(&self) -> QueryBuilder<'_> {
+        QueryBuilder::new(self)
+    }
+}
+
+// Returns the partition and sort key of the table or of the index `index`
+fn key_schema<'i>(
+    handle: &'i TableHandle,
+    index: Option<&str>,
+) -> std::result::Result<(&'i TableAttr, Option<&'i TableAttr>), String> {
+    let info = handle.info();
+    let Some(index) = index else {
+        return Ok((&info.pk, info.sk.as_ref()));
+    };
+    if let Some(gsi) = info.gsis.iter().find(|g| g.name == index) {
+        return Ok((&gsi.pk, gsi.sk.as_ref()));
+    }
+    if let Some(lsi) = info.lsis.iter().find(|l| l.name == index) {
+        return Ok((&lsi.pk, Some(&lsi.sk)));
+    }
+    Err(format!("the table has no index '{}'", index))
+}
+
+// Projections use `#qp0`, `#qp1`, ...
+fn is_projection_placeholder(placeholder: &str) -> bool {
+    placeholder
+        .strip_prefix("#qp")
+        .is_some_and(|n| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit()))
+}
+
+fn check_type(value: &AttributeValue, attr: &TableAttr) -> std::result::Result<(), String> {
+    let matches = match attr.attr_type {
+        AttrType::S => value.is_s(),
+        AttrType::N => value.is_n(),
+        AttrType::B => value.is_b(),
+    };
+    if matches {
+        Ok(())
+    } else {
+        Err(format!(
+            "key '{}' is of type {:?}, got {:?}",
+            attr.name, attr.attr_type, value
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{TableConfig, TableGsi};
+    use aws_sdk_dynamodb::Client;
+
+    fn users() -> TableHandle {
+        let mut info = TableConfig::load_from_file("fixtures/dev.yml")
+            .unwrap()
+            .tables
+            .remove(0);
+        info.gsis.push(TableGsi::new(
+            "by_email",
+            TableAttr {
+                name: "email".to_string(),
+                attr_type: AttrType::S,
+            },
+            None,
+        ));
+        let config = aws_sdk_dynamodb::Config::builder()
+            .behavior_version_latest()
+            .build();
+        TableHandle::new(Client::from_conf(config), "users-test".to_string(), info)
+    }
+
+    fn invalid(builder: QueryBuilder<'_>) -> String {
+        match builder.request() {
+            Err(DynamoToolsError::InvalidQuery(_, msg)) => msg,
+            other => panic!("expected an invalid query, got {:?}", other.map(|_| ())),
+        }
+    }
+
+    #[test]
+    fn query_should_use_key_schema_of_index() {
+        let users = users();
+        let request = users
+            .query_builder()
+            .index("gsi1")
+            .pk_eq("email#alice")
+            .sk_between("a", "m")
+            .filter("#name <> :name")
+            .name("#name", "name")
+            .value(":name", "Bob")
+            .project(["pk", "name"])
+            .descending()
+            .request()
+            .unwrap();
+        let input = request.as_input();
+
+        assert_eq!(input.get_index_name().as_deref(), Some("gsi1"));
+        assert_eq!(
+            input.get_key_condition_expression().as_deref(),
+            Some("#qpk = :qpk AND #qsk BETWEEN :qsk AND :qsk2")
+        );
+        assert_eq!(
+            input.get_projection_expression().as_deref(),
+            Some("#qp0, #qp1")
+        );
+        assert_eq!(input.get_scan_index_forward(), &Some(false));
+        let names = input.get_expression_attribute_names().as_ref().unwrap();
+        assert_eq!(names["#qpk"], "gsi1pk");
+        assert_eq!(names["#qsk"], "gsi1sk");
+        assert_eq!(names["#qp1"], "name");
+        let values = input.get_expression_attribute_values().as_ref().unwrap();
+        assert_eq!(values[":qsk2"], AttributeValue::S("m".to_string()));
+        assert_eq!(values.len(), 4);
+    }
+
+    #[test]
+    fn filter_conditions_should_share_placeholders() {
+        let users = users();
+        let request = users
+            .query_builder()
+            .pk_eq("user_1")
+            .filter("#age > :age")
+            .name("#age", "age")
+            .value(":age", 18)
+            .filter_condition(&crate::attr("name").eq("Bob"))
+            .filter_condition(&crate::attr("name").ne("Alice"))
+            .request()
+            .unwrap();
+        let input = request.as_input();
+        assert_eq!(
+            input.get_filter_expression().as_deref(),
+            Some("(#age > :age) AND (#n0 = :v0) AND (#n0 <> :v1)")
+        );
+        let names = input.get_expression_attribute_names().as_ref().unwrap();
+        assert_eq!(names["#n0"], "name");
+        assert_eq!(names.len(), 3);
+        let values = input.get_expression_attribute_values().as_ref().unwrap();
+        assert_eq!(values[":v1"], AttributeValue::S("Alice".to_string()));
+        assert_eq!(values.len(), 4);
+
+        let clash = users
+            .query_builder()
+            .pk_eq("user_1")
+            .filter_condition(&crate::attr("name").eq("Bob"))
+            .value(":v0", 1);
+        assert!(invalid(clash).contains("already used"));
+    }
+
+    #[test]
+    fn impossible_queries_should_be_rejected() {
+        let users = users();
+        assert!(invalid(users.query_builder()).contains("pk_eq"));
+        assert!(invalid(users.query_builder().index("nope").pk_eq("a")).contains("no index"));
+        assert!(
+            invalid(
+                users
+                    .query_builder()
+                    .index("by_email")
+                    .pk_eq("a")
+                    .sk_eq("b")
+            )
+            .contains("no sort key")
+        );
+        assert!(invalid(users.query_builder().pk_eq(1)).contains("type S"));
+        assert!(
+            invalid(
+                users
+                    .query_builder()
+                    .index("gsi1")
+                    .pk_eq("a")
+                    .consistent_read()
+            )
+            .contains("consistent reads")
+        );
+        assert!(invalid(users.query_builder().pk_eq("a").value(":qpk", "b")).contains("reserved"));
+        assert!(
+            invalid(
+                users
+                    .query_builder()
+                    .pk_eq("a")
+                    .filter("#qp0 = :x")
+                    .name("#qp0", "name")
+            )
+            .contains("'#qp0' is reserved")
+        );
+        // Only the numbered placeholders are taken
+        assert!(
+            users
+                .query_builder()
+                .pk_eq("a")
+                .name("#qpx", "name")
+                .request()
+                .is_ok()
+        );
+        // LSIs share the partition key of the table
+        assert!(
+            users
+                .query_builder()
+                .index("lsi1")
+                .pk_eq("user_1")
+                .sk_begins_with("2024")
+                .consistent_read()
+                .request()
+                .is_ok()
+        );
+    }
+}
@@ -0,0 +1,81 @@
+use crate::error::{DynamoToolsError, Result};
+use crate::item::Item;
+use aws_sdk_dynamodb::Client;
+use futures::future::try_join_all;
+use std::collections::HashMap;
+
+/// Scans every item of `table_name`, following pagination.
+///
+/// Scans are strongly consistent, so they see every write made before them
+/// (snapshots, restores, truncates and assertions rely on this).
+///
+/// When `segments` is greater than 1, the table is scanned with that many
+/// parallel segments and the results are concatenated.
+pub(crate) async fn scan_all(
+    client: &Client,
+    table_name: &str,
+    segments: i32,
+) -> Result<Vec<Item>> {
+    scan_segments(client, table_name, segments, None).await
+}
+
+/// Scans only the primary key attributes (`key_names`) of every item of `table_name`.
+pub(crate) async fn scan_keys(
+    client: &Client,
+    table_name: &str,
+    key_names: &[String],
+) -> Result<Vec<Item>> {
+    scan_segments(client, table_name, 1, Some(key_names)).await
+}
+
+async fn scan_segments(
+    client: &Client,
+    table_name: &str,
+    segments: i32,
+    projection: Option<&[String]>,
+) -> Result<Vec<Item>> {
+    if segments <= 1 {
+        return scan_segment(client, table_name, None, projection).await;
+    }
+    let scans = (0..segments)
+        .map(|segment| scan_segment(client, table_name, Some((segment, segments)), projection));
+    Ok(try_join_all(scans).await?.into_iter().flatten().collect())
+}
+
+async fn scan_segment(
+    client: &Client,
+    table_name: &str,
+    segment: Option<(i32, i32)>,
+    projection: Option<&[String]>,
+) -> Result<Vec<Item>> {
+    let mut items = Vec::new();
+    let mut start_key: Option<HashMap<_, _>> = None;
+    loop {
+        let mut request = client
+            .scan()
+            .table_name(table_name)
+            .consistent_read(true)
+            .set_exclusive_start_key(start_key.take());
+        if let Some((segment, total)) = segment {
+            request = request.segment(segment).total_segments(total);
+        }
+        if let Some(names) = projection {
+            // Use placeholders so reserved words can be projected
+            let placeholders: Vec<String> = (0..names.len()).map(|i| format!("#k{}", i)).collect();
+            request = request.projection_expression(placeholders.join(", "));
+            for (placeholder, name) in placeholders.into_iter().zip(names) {
+                request = request.expression_attribute_names(placeholder, name);
+            }
+        }
+        let resp = request
+            .send()
+            .await
+            .map_err(|e| DynamoToolsError::Scan(table_name.to_string(), e))?;
+        items.extend(resp.items.unwrap_or_default());
+        match resp.last_evaluated_key {
+            Some(key) if !key.is_empty() => start_key = Some(key),
+            _ => break,
+        }
+    }
+    Ok(items)
+}
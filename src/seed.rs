@@ -0,0 +1,282 @@
+use crate::SeedMode;
+use crate::error::{DynamoToolsError, Result};
+use crate::item::{self, Item, extract_key};
+use aws_sdk_dynamodb::Client;
+use aws_sdk_dynamodb::config::AsyncSleep;
+use aws_sdk_dynamodb::error::SdkError;
+use aws_sdk_dynamodb::operation::batch_write_item::BatchWriteItemError;
+use aws_sdk_dynamodb::types::{DeleteRequest, Put, PutRequest, TransactWriteItem, WriteRequest};
+use std::collections::HashMap;
+use std::collections::hash_map::RandomState;
+use std::fs;
+use std::hash::{BuildHasher, Hasher};
+use std::path::Path;
+use std::time::Duration;
+
+// Maximum number of requests accepted by a single BatchWriteItem call
+const BATCH_WRITE_LIMIT: usize = 25;
+// Maximum number of actions accepted by a single TransactWriteItems call
+const TRANSACT_WRITE_LIMIT: usize = 100;
+// Number of times unprocessed items are resent before giving up
+const BATCH_WRITE_RETRIES: u32 = 8;
+// Backoff before the first resend, doubled on every further attempt
+const BACKOFF_BASE: Duration = Duration::from_millis(50);
+const BACKOFF_MAX: Duration = Duration::from_secs(5);
+
+/// Reads the items in `path` and converts them to DynamoDB items.
+///
+/// Any [`crate::DataFormat`] is accepted; the format is detected from the content.
+pub(crate) fn read_seed_file(path: &Path) -> Result<Vec<Item>> {
+    let path_str = path.to_string_lossy().to_string();
+    let content = fs::read_to_string(path)
+        .map_err(|e| DynamoToolsError::SeedFileRead(path_str.clone(), e))?;
+    item::parse_items(&content, &path_str)
+}
+
+/// Writes `items` into `table_name` according to `mode`.
+///
+/// `key_names` are the primary key attribute names of the table, used for
+/// conditional puts and for cleaning up after a failed transactional seed.
+pub(crate) async fn write_items(
+    client: &Client,
+    table_name: &str,
+    key_names: &[String],
+    items: Vec<Item>,
+    mode: SeedMode,
+) -> Result<()> {
+    match mode {
+        SeedMode::Overwrite => overwrite(client, table_name, items).await,
+        SeedMode::InsertIfAbsent => insert_if_absent(client, table_name, key_names, items).await,
+        SeedMode::AllOrNothing => all_or_nothing(client, table_name, key_names, items).await,
+    }
+}
+
+/// Sends `requests` with `BatchWriteItem` in chunks of 25, resubmitting unprocessed
+/// items with exponential backoff and jitter.
+///
+/// `sdk_err` builds the error returned when a request fails, so callers can
+/// report it in their own terms (e.g. [`DynamoToolsError::SeedBatchWrite`]).
+///
+/// # Errors
+///
+/// Returns `Err` ([`DynamoToolsError::BatchWriteUnprocessed`]) if items are still
+/// unprocessed after `BATCH_WRITE_RETRIES` resends.
+pub(crate) async fn batch_write(
+    client: &Client,
+    table_name: &str,
+    requests: Vec<WriteRequest>,
+    sdk_err: fn(String, SdkError<BatchWriteItemError>) -> DynamoToolsError,
+) -> Result<()> {
+    let sleep = client.config().sleep_impl();
+    for chunk in requests.chunks(BATCH_WRITE_LIMIT) {
+        let mut pending = HashMap::from([(table_name.to_string(), chunk.to_vec())]);
+        let mut attempt = 0;
+        while !pending.is_empty() {
+            if attempt > 0 {
+                if attempt > BATCH_WRITE_RETRIES {
+                    let count = pending.values().map(Vec::len).sum();
+                    return Err(DynamoToolsError::BatchWriteUnprocessed(
+                        table_name.to_string(),
+                        count,
+                    ));
+                }
+                // Without a sleep implementation (a client built without an async
+                // runtime), retries are only bounded by the retry cap
+                if let Some(sleep) = &sleep {
+                    sleep.sleep(backoff(attempt)).await;
+                }
+            }
+            attempt += 1;
+            let resp = client
+                .batch_write_item()
+                .set_request_items(Some(pending))
+                .send()
+                .await
+                .map_err(|e| sdk_err(table_name.to_string(), e))?;
+            pending = resp.unprocessed_items.unwrap_or_default();
+            pending.retain(|_, reqs| !reqs.is_empty());
+        }
+    }
+    Ok(())
+}
+
+// Full jitter: a random delay between zero and the exponential backoff for `attempt`
+fn backoff(attempt: u32) -> Duration {
+    let ceiling = BACKOFF_BASE
+        .saturating_mul(1 << (attempt - 1).min(16))
+        .min(BACKOFF_MAX);
+    let random = RandomState::new().build_hasher().finish();
+    ceiling.mul_f64((random % 1_000) as f64 / 1_000.0)
+}
+
+/// Builds a `WriteRequest` that puts `item`.
+pub(crate) fn put_request(item: Item) -> Result<WriteRequest> {
+    let put_request = PutRequest::builder()
+        .set_item(Some(item))
+        .build()
+        .map_err(|e| DynamoToolsError::Internal(format!("Failed to build PutRequest: {}", e)))?;
+    Ok(WriteRequest::builder().put_request(put_request).build())
+}
+
+/// Builds a `WriteRequest` that deletes the item identified by `key`.
+pub(crate) fn delete_request(key: Item) -> Result<WriteRequest> {
+    let delete_request = DeleteRequest::builder()
+        .set_key(Some(key))
+        .build()
+        .map_err(|e| DynamoToolsError::Internal(format!("Failed to build DeleteRequest: {}", e)))?;
+    Ok(WriteRequest::builder()
+        .delete_request(delete_request)
+        .build())
+}
+
+async fn overwrite(client: &Client, table_name: &str, items: Vec<Item>) -> Result<()> {
+    let count = items.len();
+    let requests = items
+        .into_iter()
+        .map(put_request)
+        .collect::<Result<Vec<_>>>()?;
+    batch_write(
+        client,
+        table_name,
+        requests,
+        DynamoToolsError::SeedBatchWrite,
+    )
+    .await?;
+    println!("[INFO] Wrote {} items to table '{}'", count, table_name);
+    Ok(())
+}
+
+async fn insert_if_absent(
+    client: &Client,
+    table_name: &str,
+    key_names: &[String],
+    items: Vec<Item>,
+) -> Result<()> {
+    let mut written = 0;
+    let mut skipped = 0;
+    for item in items {
+        let result = client
+            .put_item()
+            .table_name(table_name)
+            .set_item(Some(item))
+            .condition_expression("attribute_not_exists(#pk)")
+            .expression_attribute_names("#pk", &key_names[0])
+            .send()
+            .await;
+        match result {
+            Ok(_) => written += 1,
+            Err(e)
+                if e.as_service_error()
+                    .is_some_and(|se| se.is_conditional_check_failed_exception()) =>
+            {
+                skipped += 1
+            }
+            Err(e) => return Err(DynamoToolsError::SeedPut(table_name.to_string(), e)),
+        }
+    }
+    println!(
+        "[INFO] Wrote {} items to table '{}', skipped {} existing",
+        written, table_name, skipped
+    );
+    Ok(())
+}
+
+async fn all_or_nothing(
+    client: &Client,
+    table_name: &str,
+    key_names: &[String],
+    items: Vec<Item>,
+) -> Result<()> {
+    // Validate keys up front so a malformed item fails before anything is written
+    let keys = items
+        .iter()
+        .map(|item| extract_key(item, key_names))
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut written = 0;
+    for chunk in items.chunks(TRANSACT_WRITE_LIMIT) {
+        let actions = chunk
+            .iter()
+            .map(|item| {
+                // Only create items, so the cleanup below never deletes an item
+                // that existed before seeding
+                let put = Put::builder()
+                    .table_name(table_name)
+                    .set_item(Some(item.clone()))
+                    .condition_expression("attribute_not_exists(#pk)")
+                    .expression_attribute_names("#pk", &key_names[0])
+                    .build()
+                    .map_err(|e| {
+                        DynamoToolsError::Internal(format!("Failed to build Put: {}", e))
+                    })?;
+                Ok(TransactWriteItem::builder().put(put).build())
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let result = client
+            .transact_write_items()
+            .set_transact_items(Some(actions))
+            .send()
+            .await;
+
+        if let Err(e) = result {
+            eprintln!(
+                "[ERROR] Seeding table '{}' failed, removing {} items already written",
+                table_name, written
+            );
+            let cleanup = keys[..written]
+                .iter()
+                .cloned()
+                .map(delete_request)
+                .collect::<Result<Vec<_>>>()?;
+            if let Err(cleanup_err) = batch_write(
+                client,
+                table_name,
+                cleanup,
+                DynamoToolsError::SeedBatchWrite,
+            )
+            .await
+            {
+                eprintln!(
+                    "[ERROR] Failed to clean up table '{}': {}",
+                    table_name, cleanup_err
+                );
+            }
+            return Err(DynamoToolsError::SeedTransactWrite(
+                table_name.to_string(),
+                e,
+            ));
+        }
+        written += chunk.len();
+    }
+    println!(
+        "[INFO] Transactionally wrote {} items to table '{}'",
+        written, table_name
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff_should_grow_and_stay_capped() {
+        let draws = |attempt| (0..200).map(|_| backoff(attempt)).collect::<Vec<_>>();
+        // Full jitter: each delay lies between zero and 50ms * 2^(attempt - 1)
+        for (attempt, ceiling) in [(1, 50), (2, 100), (4, 400), (7, 3_200)] {
+            let ceiling = Duration::from_millis(ceiling);
+            assert!(draws(attempt).iter().all(|d| *d <= ceiling), "{}", attempt);
+        }
+        // Capped at 5s, however many attempts
+        for attempt in [8, 9, 16, 30, u32::MAX] {
+            assert!(draws(attempt).iter().all(|d| *d <= Duration::from_secs(5)));
+        }
+        // Delays grow with the attempt number and are spread over the whole range
+        let longest = |attempt| draws(attempt).into_iter().max().unwrap();
+        assert!(longest(5) > Duration::from_millis(50));
+        assert!(longest(12) > Duration::from_millis(2_500));
+        let first = draws(6);
+        assert!(first.iter().any(|d| *d != first[0]));
+    }
+}
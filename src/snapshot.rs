@@ -0,0 +1,68 @@
+use crate::error::{DynamoToolsError, Result};
+use crate::item::{self, DataFormat, Item};
+use std::fs;
+use std::path::Path;
+
+/// A point-in-time copy of all items of a table created by a [`crate::DynamodbConnector`].
+///
+/// Taken with [`crate::DynamodbConnector::snapshot`] and applied with
+/// [`crate::DynamodbConnector::restore`]. Snapshots live in memory, but can be
+/// saved to and loaded from disk (as DynamoDB JSON) to share expensive seeded
+/// states between test runs.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Snapshot {
+    base_name: String,
+    items: Vec<Item>,
+}
+
+impl Snapshot {
+    /// Creates a snapshot of `items` for the table with the given base name.
+    pub fn new(base_name: impl Into<String>, items: Vec<Item>) -> Self {
+        Self {
+            base_name: base_name.into(),
+            items,
+        }
+    }
+
+    /// Loads a snapshot previously written by [`Snapshot::save`] (or any other
+    /// file in a supported [`DataFormat`]) for the table with the given base name.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` ([`DynamoToolsError::SnapshotRead`] /
+    /// [`DynamoToolsError::SnapshotParse`]) if the file cannot be read or parsed.
+    pub fn load(base_name: impl Into<String>, path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let path_str = path.to_string_lossy().to_string();
+        let content = fs::read_to_string(path)
+            .map_err(|e| DynamoToolsError::SnapshotRead(path_str.clone(), e))?;
+        let items = item::parse_items(&content, &path_str).map_err(|e| match e {
+            DynamoToolsError::SeedJsonParse(path, e) => DynamoToolsError::SnapshotParse(path, e),
+            e => e,
+        })?;
+        Ok(Self::new(base_name, items))
+    }
+
+    /// Saves the snapshot items to `path` as DynamoDB JSON.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` ([`DynamoToolsError::SnapshotWrite`]) if the file cannot be written.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        let mut content = Vec::new();
+        item::write_items(&mut content, &self.items, DataFormat::DynamoDbJson)?;
+        fs::write(path, content)
+            .map_err(|e| DynamoToolsError::SnapshotWrite(path.to_string_lossy().to_string(), e))
+    }
+
+    /// The base name of the table this snapshot was taken from.
+    pub fn base_name(&self) -> &str {
+        &self.base_name
+    }
+
+    /// The items captured by this snapshot.
+    pub fn items(&self) -> &[Item] {
+        &self.items
+    }
+}
@@ -0,0 +1,179 @@
+use crate::connector::{disable_deletion_protection, wait_for_active};
+use crate::error::{DynamoToolsError, Result};
+use aws_sdk_dynamodb::Client;
+use aws_sdk_dynamodb::types::{DeleteReplicationGroupMemberAction, ReplicationGroupUpdate};
+use std::str::FromStr;
+use std::time::{Duration, SystemTime};
+
+/// Selects the tables considered by [`sweep_orphans`].
+///
+/// Only tables named `<base>-<xid>` (as created by [`crate::DynamodbConnector`])
+/// are ever considered, whatever the target.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SweepTarget {
+    /// Tables whose base name is one of the given names.
+    BaseNames(Vec<String>),
+    /// Tables whose name starts with the given prefix.
+    Prefix(String),
+}
+
+/// Deletes tables left behind by connectors that never got to clean up
+/// (e.g. because the test process panicked or was killed).
+///
+/// Lists all tables, keeps those matching `target`, recovers their creation time
+/// from the xid suffix of the name and deletes the ones older than `older_than`.
+/// Returns the names of the deleted tables.
+///
+/// # Errors
+///
+/// Returns `Err` if listing tables fails. Failures to delete individual tables
+/// are logged and skipped. Deletion protection and replicas of swept tables are
+/// removed first.
+pub async fn sweep_orphans(
+    client: &Client,
+    target: &SweepTarget,
+    older_than: Duration,
+) -> Result<Vec<String>> {
+    let now = SystemTime::now();
+    let mut deleted = Vec::new();
+    let mut start_table: Option<String> = None;
+    loop {
+        let resp = client
+            .list_tables()
+            .set_exclusive_start_table_name(start_table.take())
+            .send()
+            .await
+            .map_err(DynamoToolsError::ListTables)?;
+
+        for name in resp.table_names() {
+            let Some(age) = orphan_age(name, target, now) else {
+                continue;
+            };
+            if age < older_than {
+                continue;
+            }
+            if let Err(e) = prepare_for_deletion(client, name).await {
+                eprintln!("[ERROR] Failed to prepare '{}' for deletion: {}", name, e);
+                continue;
+            }
+            match client.delete_table().table_name(name).send().await {
+                Ok(_) => {
+                    println!(
+                        "[INFO] Swept orphaned table '{}' (age: {}s)",
+                        name,
+                        age.as_secs()
+                    );
+                    deleted.push(name.clone());
+                }
+                Err(e) => eprintln!("[ERROR] Failed to sweep table '{}': {}", name, e),
+            }
+        }
+
+        match resp.last_evaluated_table_name {
+            Some(name) => start_table = Some(name),
+            None => break,
+        }
+    }
+    Ok(deleted)
+}
+
+/// Removes the replicas of `table_name` in `regions`, one region at a time.
+pub(crate) async fn remove_replicas(
+    client: &Client,
+    table_name: &str,
+    regions: &[String],
+) -> Result<()> {
+    for region in regions {
+        wait_for_active(client, table_name).await?;
+        let update = ReplicationGroupUpdate::builder()
+            .delete(
+                DeleteReplicationGroupMemberAction::builder()
+                    .region_name(region)
+                    .build()?,
+            )
+            .build();
+        client
+            .update_table()
+            .table_name(table_name)
+            .replica_updates(update)
+            .send()
+            .await
+            .map_err(|e| DynamoToolsError::ReplicaUpdate(table_name.to_string(), e))?;
+        println!("[INFO] Removed replica of '{}' in {}", table_name, region);
+    }
+    if !regions.is_empty() {
+        wait_for_active(client, table_name).await?;
+    }
+    Ok(())
+}
+
+// Lifts the deletion protection of `table_name` and removes every replica other than
+// the one in the client's own region, since either blocks `DeleteTable`
+async fn prepare_for_deletion(client: &Client, table_name: &str) -> Result<()> {
+    let resp = client
+        .describe_table()
+        .table_name(table_name)
+        .send()
+        .await
+        .map_err(DynamoToolsError::TableDescribe)?;
+    if resp
+        .table()
+        .and_then(|t| t.deletion_protection_enabled())
+        .unwrap_or_default()
+    {
+        disable_deletion_protection(client, table_name).await?;
+    }
+    let own_region = client.config().region().map(|r| r.as_ref());
+    let regions: Vec<String> = resp
+        .table()
+        .map(|t| t.replicas())
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|r| r.region_name())
+        .filter(|region| Some(*region) != own_region)
+        .map(str::to_string)
+        .collect();
+    remove_replicas(client, table_name, &regions).await
+}
+
+// Returns the age of `name` if it is a connector-created table matching `target`
+fn orphan_age(name: &str, target: &SweepTarget, now: SystemTime) -> Option<Duration> {
+    let (base, suffix) = name.rsplit_once('-')?;
+    let matches = match target {
+        SweepTarget::BaseNames(names) => names.iter().any(|n| n == base),
+        SweepTarget::Prefix(prefix) => name.starts_with(prefix.as_str()),
+    };
+    if !matches {
+        return None;
+    }
+    let id = xid::Id::from_str(suffix).ok()?;
+    // A creation time in the future (clock skew) counts as brand new
+    Some(now.duration_since(id.time()).unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn orphan_age_should_only_match_connector_tables() {
+        let id = xid::new();
+        let now = id.time() + Duration::from_secs(90);
+        let users = SweepTarget::BaseNames(vec!["users".to_string()]);
+        let prefix = SweepTarget::Prefix("us".to_string());
+
+        let name = format!("users-{}", id);
+        assert_eq!(
+            orphan_age(&name, &users, now),
+            Some(Duration::from_secs(90))
+        );
+        assert_eq!(
+            orphan_age(&name, &prefix, now),
+            Some(Duration::from_secs(90))
+        );
+
+        assert_eq!(orphan_age(&format!("orders-{}", id), &users, now), None);
+        assert_eq!(orphan_age("users-not_an_xid", &users, now), None);
+        assert_eq!(orphan_age("users", &prefix, now), None);
+    }
+}
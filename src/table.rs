@@ -0,0 +1,249 @@
+use crate::error::{DynamoToolsError, Result};
+use crate::expression::{Condition, Expressions, Update};
+use crate::item::{self, Item};
+use crate::{TableInfo, scan};
+use aws_sdk_dynamodb::Client;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use std::collections::HashMap;
+
+/// A table created by a [`crate::DynamodbConnector`], bundling its real name,
+/// its schema and a client.
+///
+/// Obtained with [`crate::DynamodbConnector::table`]. Items are converted from and
+/// to Rust values with `serde_dynamo`.
+#[derive(Debug, Clone)]
+pub struct TableHandle {
+    client: Client,
+    table_name: String,
+    info: TableInfo,
+}
+
+impl TableHandle {
+    pub(crate) fn new(client: Client, table_name: String, info: TableInfo) -> Self {
+        Self {
+            client,
+            table_name,
+            info,
+        }
+    }
+
+    /// Returns the real (unique) name of the table.
+    pub fn name(&self) -> &str {
+        &self.table_name
+    }
+
+    /// Returns the configured schema of the table.
+    pub fn info(&self) -> &TableInfo {
+        &self.info
+    }
+
+    /// Returns the client used for the table.
+    pub fn client(&self) -> &Client {
+        &self.client
+    }
+
+    /// Writes `item`, replacing any item with the same key.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if the item cannot be converted ([`DynamoToolsError::ItemConversion`])
+    /// or `PutItem` fails ([`DynamoToolsError::PutItem`]).
+    pub async fn put<T: Serialize>(&self, item: &T) -> Result<()> {
+        let item = self.encode(item)?;
+        self.client
+            .put_item()
+            .table_name(&self.table_name)
+            .set_item(Some(item))
+            .send()
+            .await
+            .map_err(|e| DynamoToolsError::PutItem(self.table_name.clone(), e))?;
+        Ok(())
+    }
+
+    /// Reads the item with the given key, if any.
+    ///
+    /// `key` is any value serializing to a map holding the primary key attributes,
+    /// e.g. `json!({ "pk": "user_1", "sk": "profile" })` or the item itself; other
+    /// attributes are ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if the key misses a key attribute ([`DynamoToolsError::MissingField`]),
+    /// `GetItem` fails ([`DynamoToolsError::GetItem`]) or the item cannot be converted
+    /// to `T` ([`DynamoToolsError::ItemConversion`]).
+    pub async fn get<K: Serialize, T: DeserializeOwned>(&self, key: &K) -> Result<Option<T>> {
+        let resp = self
+            .client
+            .get_item()
+            .table_name(&self.table_name)
+            .set_key(Some(self.key(key)?))
+            .consistent_read(true)
+            .send()
+            .await
+            .map_err(|e| DynamoToolsError::GetItem(self.table_name.clone(), e))?;
+        resp.item.map(|item| self.decode(item)).transpose()
+    }
+
+    /// Deletes the item with the given key (see [`TableHandle::get`] for `key`).
+    /// Deleting a missing item is not an error.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if the key misses a key attribute ([`DynamoToolsError::MissingField`])
+    /// or `DeleteItem` fails ([`DynamoToolsError::DeleteItem`]).
+    pub async fn delete<K: Serialize>(&self, key: &K) -> Result<()> {
+        self.client
+            .delete_item()
+            .table_name(&self.table_name)
+            .set_key(Some(self.key(key)?))
+            .send()
+            .await
+            .map_err(|e| DynamoToolsError::DeleteItem(self.table_name.clone(), e))?;
+        Ok(())
+    }
+
+    /// Applies `update` to the item with the given key (see [`TableHandle::get`] for
+    /// `key`), creating the item if it does not exist. With a `condition`, the
+    /// update only happens if the condition holds for the current item.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if the key misses a key attribute ([`DynamoToolsError::MissingField`]),
+    /// an expression is invalid ([`DynamoToolsError::InvalidExpression`]) or `UpdateItem`
+    /// fails ([`DynamoToolsError::UpdateItem`]), including when the condition does not hold.
+    pub async fn update<K: Serialize>(
+        &self,
+        key: &K,
+        update: &Update,
+        condition: Option<&Condition>,
+    ) -> Result<()> {
+        let mut exprs = Expressions::new();
+        let update = exprs.update(update)?;
+        let condition = condition.map(|c| exprs.condition(c)).transpose()?;
+        self.client
+            .update_item()
+            .table_name(&self.table_name)
+            .set_key(Some(self.key(key)?))
+            .update_expression(update)
+            .set_condition_expression(condition)
+            .set_expression_attribute_names(exprs.names())
+            .set_expression_attribute_values(exprs.values())
+            .send()
+            .await
+            .map_err(|e| DynamoToolsError::UpdateItem(self.table_name.clone(), e))?;
+        Ok(())
+    }
+
+    /// Returns every item whose partition key is `pk`, in sort key order,
+    /// following pagination.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if `pk` cannot be converted, `Query` fails
+    /// ([`DynamoToolsError::Query`]) or an item cannot be converted to `T`.
+    pub async fn query<P: Serialize, T: DeserializeOwned>(&self, pk: &P) -> Result<Vec<T>> {
+        let pk = serde_dynamo::to_attribute_value(pk)
+            .map_err(|e| DynamoToolsError::ItemConversion(self.table_name.clone(), e))?;
+        let request = self
+            .client
+            .query()
+            .table_name(&self.table_name)
+            .key_condition_expression("#pk = :pk")
+            .expression_attribute_names("#pk", &self.info.pk.name)
+            .expression_attribute_values(":pk", pk);
+
+        let mut items = Vec::new();
+        let mut start_key: Option<HashMap<_, _>> = None;
+        loop {
+            let resp = request
+                .clone()
+                .set_exclusive_start_key(start_key.take())
+                .send()
+                .await
+                .map_err(|e| DynamoToolsError::Query(self.table_name.clone(), e))?;
+            for item in resp.items.unwrap_or_default() {
+                items.push(self.decode(item)?);
+            }
+            match resp.last_evaluated_key {
+                Some(key) if !key.is_empty() => start_key = Some(key),
+                _ => break,
+            }
+        }
+        Ok(items)
+    }
+
+    /// Returns every item of the table, following pagination.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if `Scan` fails ([`DynamoToolsError::Scan`]) or an item cannot
+    /// be converted to `T`.
+    pub async fn scan<T: DeserializeOwned>(&self) -> Result<Vec<T>> {
+        scan::scan_all(&self.client, &self.table_name, 1)
+            .await?
+            .into_iter()
+            .map(|item| self.decode(item))
+            .collect()
+    }
+
+    fn encode<T: Serialize>(&self, value: &T) -> Result<Item> {
+        serde_dynamo::to_item(value)
+            .map_err(|e| DynamoToolsError::ItemConversion(self.table_name.clone(), e))
+    }
+
+    fn decode<T: DeserializeOwned>(&self, item: Item) -> Result<T> {
+        serde_dynamo::from_item(item)
+            .map_err(|e| DynamoToolsError::ItemConversion(self.table_name.clone(), e))
+    }
+
+    // Converts `key` and keeps only the primary key attributes
+    fn key<K: Serialize>(&self, key: &K) -> Result<Item> {
+        item::extract_key(&self.encode(key)?, &self.info.key_names())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AttrType, TableAttr};
+    use aws_sdk_dynamodb::types::AttributeValue;
+    use serde_json::json;
+
+    fn handle() -> TableHandle {
+        let attr = |name: &str, attr_type| TableAttr {
+            name: name.to_string(),
+            attr_type,
+        };
+        let config = aws_sdk_dynamodb::Config::builder()
+            .behavior_version_latest()
+            .build();
+        TableHandle::new(
+            Client::from_conf(config),
+            "users-test".to_string(),
+            TableInfo::new(
+                "users",
+                attr("pk", AttrType::S),
+                Some(attr("sk", AttrType::N)),
+            ),
+        )
+    }
+
+    #[test]
+    fn key_should_keep_only_key_attributes() {
+        let handle = handle();
+        let key = handle
+            .key(&json!({ "pk": "user_1", "sk": 2, "name": "Alice" }))
+            .unwrap();
+        assert_eq!(
+            key,
+            HashMap::from([
+                ("pk".to_string(), AttributeValue::S("user_1".to_string())),
+                ("sk".to_string(), AttributeValue::N("2".to_string())),
+            ])
+        );
+
+        let err = handle.key(&json!({ "pk": "user_1" })).unwrap_err();
+        assert!(matches!(err, DynamoToolsError::MissingField(_)));
+    }
+}
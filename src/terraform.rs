@@ -0,0 +1,624 @@
+use crate::config::defined_attr;
+use crate::error::{DynamoToolsError, Result};
+use crate::{
+    AttrType, Encryption, Replica, TableAttr, TableConfig, TableGsi, TableInfo, TableLsi,
+    Throughput, WarmThroughput,
+};
+use aws_sdk_dynamodb::operation::create_table::CreateTableInput;
+use aws_sdk_dynamodb::types::{
+    BillingMode, KeySchemaElement, KeyType, OnDemandThroughput, Projection, ProvisionedThroughput,
+    WarmThroughput as SdkWarmThroughput,
+};
+use hcl::{Block, BlockBuilder, Body, Expression};
+use serde::de::DeserializeOwned;
+use std::collections::BTreeMap;
+
+impl TableConfig {
+    /// Renders every table as an `aws_dynamodb_table` Terraform resource block
+    /// (requires the `terraform` feature).
+    ///
+    /// Each resource is labelled with the base name (made a valid identifier) and
+    /// uses the base name as `name`. Key schema, `attribute`, `global_secondary_index`,
+    /// `local_secondary_index`, billing, throughput, `ttl`, streams, encryption,
+    /// table class, deletion protection, point-in-time recovery, `replica` regions and
+    /// the merged [`TableConfig::table_tags`] are included.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if a table cannot be converted, its `encryption.kms_key_id` is
+    /// not a key ARN (Terraform's `kms_key_arn` accepts no key ids or aliases) or the
+    /// HCL cannot be rendered ([`DynamoToolsError::TemplateRender`]).
+    pub fn to_terraform(&self) -> Result<String> {
+        let mut body = Body::builder();
+        for table in &self.tables {
+            body = body.add_block(terraform_resource(self, table)?);
+        }
+        hcl::to_string(&body.build()).map_err(|e| DynamoToolsError::TemplateRender(e.to_string()))
+    }
+}
+
+impl TableInfo {
+    /// Imports the `aws_dynamodb_table` resource blocks of Terraform configuration
+    /// (requires the `terraform` feature), so tests can use the deployed schema.
+    ///
+    /// The `name` becomes the base name, falling back to the resource label when it
+    /// is not a literal (e.g. `"${var.env}-users"`). Other non-literal values are
+    /// ignored, except capacity values and KMS keys. Attributes that are not primary
+    /// keys end up in [`TableInfo::attrs`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` ([`DynamoToolsError::TemplateParse`]) if the HCL cannot be
+    /// parsed, a table has no usable key schema, a capacity value is not a literal
+    /// number (e.g. `var.read_capacity`) or `kms_key_arn` is not a literal string.
+    pub fn from_terraform(hcl: &str) -> Result<Vec<TableInfo>> {
+        let body = hcl::parse(hcl).map_err(|e| DynamoToolsError::TemplateParse(e.to_string()))?;
+        body.blocks()
+            .filter(|b| {
+                b.identifier() == "resource"
+                    && b.labels().first().map(|l| l.as_str()) == Some("aws_dynamodb_table")
+            })
+            .map(|b| {
+                let label = b.labels().get(1).map(|l| l.as_str()).unwrap_or_default();
+                import_table(label, b.body())
+            })
+            .collect()
+    }
+}
+
+fn import_table(label: &str, body: &Body) -> Result<TableInfo> {
+    let name = string(body, "name").unwrap_or(label);
+    let mut defs = BTreeMap::new();
+    for attr in blocks(body, "attribute") {
+        if let (Some(name), Some(attr_type)) = (string(attr, "name"), string(attr, "type")) {
+            defs.insert(name.to_string(), AttrType::parse(attr_type)?);
+        }
+    }
+    let key = |body: &Body, key: &str| {
+        string(body, key)
+            .map(|n| defined_attr(&defs, n))
+            .transpose()
+    };
+    let pk = key(body, "hash_key")?.ok_or_else(|| {
+        DynamoToolsError::TemplateParse(format!("table '{}' has no hash_key", name))
+    })?;
+    let mut info = TableInfo::new(name, pk, key(body, "range_key")?);
+
+    for gsi in blocks(body, "global_secondary_index") {
+        info.gsis.push(TableGsi {
+            name: string(gsi, "name").unwrap_or_default().to_string(),
+            pk: key(gsi, "hash_key")?.ok_or_else(|| {
+                DynamoToolsError::TemplateParse(format!(
+                    "a GSI of table '{}' has no hash_key",
+                    name
+                ))
+            })?,
+            sk: key(gsi, "range_key")?,
+            attrs: strings(gsi, "non_key_attributes"),
+            projection: enum_value(gsi, "projection_type"),
+            throughput: import_throughput(name, gsi)?,
+            warm_throughput: import_warm_throughput(name, gsi)?,
+            auto_scaling: None,
+        });
+    }
+    for lsi in blocks(body, "local_secondary_index") {
+        info.lsis.push(TableLsi {
+            name: string(lsi, "name").unwrap_or_default().to_string(),
+            pk: info.pk.clone(),
+            sk: key(lsi, "range_key")?.ok_or_else(|| {
+                DynamoToolsError::TemplateParse(format!(
+                    "an LSI of table '{}' has no range_key",
+                    name
+                ))
+            })?,
+            attrs: strings(lsi, "non_key_attributes"),
+            projection: enum_value(lsi, "projection_type"),
+        });
+    }
+    let key_names = info.key_names();
+    info.attrs = defs
+        .into_iter()
+        .filter(|(name, _)| !key_names.contains(name))
+        .map(|(name, attr_type)| TableAttr { name, attr_type })
+        .collect();
+
+    info.throughput = import_throughput(name, body)?;
+    info.warm_throughput = import_warm_throughput(name, body)?;
+    if let Some(ttl) = blocks(body, "ttl").next()
+        && flag(ttl, "enabled")
+    {
+        info.ttl_attribute = string(ttl, "attribute_name").map(str::to_string);
+    }
+    if flag(body, "stream_enabled") {
+        info.stream_view_type = enum_value(body, "stream_view_type");
+    }
+    if let Some(sse) = blocks(body, "server_side_encryption").next()
+        && flag(sse, "enabled")
+    {
+        info.encryption = Some(Encryption {
+            kms_key_id: key_id(name, sse, "kms_key_arn")?,
+        });
+    }
+    info.table_class = enum_value(body, "table_class");
+    info.deletion_protection = flag(body, "deletion_protection_enabled");
+    info.point_in_time_recovery =
+        blocks(body, "point_in_time_recovery").any(|pitr| flag(pitr, "enabled"));
+    info.replicas = blocks(body, "replica")
+        .filter_map(|replica| string(replica, "region_name"))
+        .map(|region| Replica {
+            region: region.to_string(),
+            gsis: Vec::new(),
+            table_class: None,
+        })
+        .collect();
+    if let Some(Expression::Object(tags)) = expr(body, "tags") {
+        for (key, value) in tags {
+            if let Expression::String(value) = value {
+                info.tags.insert(key.to_string(), value.clone());
+            }
+        }
+    }
+    Ok(info)
+}
+
+fn expr<'a>(body: &'a Body, key: &str) -> Option<&'a Expression> {
+    body.attributes().find(|a| a.key() == key).map(|a| a.expr())
+}
+
+fn blocks<'a>(body: &'a Body, identifier: &'a str) -> impl Iterator<Item = &'a Body> {
+    body.blocks()
+        .filter(move |b| b.identifier() == identifier)
+        .map(|b| b.body())
+}
+
+fn string<'a>(body: &'a Body, key: &str) -> Option<&'a str> {
+    match expr(body, key)? {
+        Expression::String(s) => Some(s),
+        _ => None,
+    }
+}
+
+fn strings(body: &Body, key: &str) -> Vec<String> {
+    match expr(body, key) {
+        Some(Expression::Array(values)) => values
+            .iter()
+            .filter_map(|v| match v {
+                Expression::String(s) => Some(s.clone()),
+                _ => None,
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+// A KMS key given by reference cannot be dropped silently either, as that would
+// turn a customer managed key into the AWS managed one
+fn key_id(table: &str, body: &Body, key: &str) -> Result<Option<String>> {
+    match expr(body, key) {
+        None | Some(Expression::Null) => Ok(None),
+        Some(Expression::String(s)) => Ok(Some(s.clone())),
+        Some(value) => Err(DynamoToolsError::TemplateParse(format!(
+            "table '{}': {} must be a literal string, got {}",
+            table, key, value
+        ))),
+    }
+}
+
+// Capacity values are the only numbers imported, and silently dropping one would
+// change the billing mode
+fn number(table: &str, body: &Body, key: &str) -> Result<Option<i64>> {
+    let Some(value) = expr(body, key) else {
+        return Ok(None);
+    };
+    let number = match value {
+        Expression::Number(n) => n.as_i64(),
+        Expression::String(s) => s.parse().ok(),
+        _ => None,
+    };
+    number.map(Some).ok_or_else(|| {
+        DynamoToolsError::TemplateParse(format!(
+            "table '{}': {} must be a literal number, got {}",
+            table, key, value
+        ))
+    })
+}
+
+fn flag(body: &Body, key: &str) -> bool {
+    match expr(body, key) {
+        Some(Expression::Bool(b)) => *b,
+        Some(Expression::String(s)) => s == "true",
+        _ => false,
+    }
+}
+
+// Parses a string attribute into one of the config enums via its serde name
+fn enum_value<T: DeserializeOwned>(body: &Body, key: &str) -> Option<T> {
+    serde_json::from_value(serde_json::Value::from(string(body, key)?)).ok()
+}
+
+fn import_throughput(table: &str, body: &Body) -> Result<Option<Throughput>> {
+    if string(body, "billing_mode") != Some("PAY_PER_REQUEST")
+        && let (Some(read), Some(write)) = (
+            number(table, body, "read_capacity")?,
+            number(table, body, "write_capacity")?,
+        )
+    {
+        return Ok(Some(Throughput::Provisioned { read, write }));
+    }
+    let Some(od) = blocks(body, "on_demand_throughput").next() else {
+        return Ok(None);
+    };
+    Ok(Some(Throughput::OnDemand {
+        max_read: number(table, od, "max_read_request_units")?,
+        max_write: number(table, od, "max_write_request_units")?,
+    }))
+}
+
+fn import_warm_throughput(table: &str, body: &Body) -> Result<Option<WarmThroughput>> {
+    let Some(warm) = blocks(body, "warm_throughput").next() else {
+        return Ok(None);
+    };
+    Ok(Some(WarmThroughput {
+        read_units_per_second: number(table, warm, "read_units_per_second")?,
+        write_units_per_second: number(table, warm, "write_units_per_second")?,
+    }))
+}
+
+/// Converts a table base name to a Terraform resource name, e.g.
+/// `user-events` becomes `user_events`.
+pub(crate) fn resource_name(table_name: &str) -> String {
+    let name: String = table_name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    if name.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("_{}", name)
+    } else {
+        name
+    }
+}
+
+fn terraform_resource(config: &TableConfig, table: &TableInfo) -> Result<Block> {
+    let input = CreateTableInput::try_from(table.clone())?;
+    let billing_mode = match input.provisioned_throughput() {
+        Some(_) => BillingMode::Provisioned,
+        None => input
+            .billing_mode()
+            .cloned()
+            .unwrap_or(BillingMode::PayPerRequest),
+    };
+
+    let mut block = Block::builder("resource")
+        .add_label("aws_dynamodb_table")
+        .add_label(resource_name(&table.table_name))
+        .add_attribute(("name", table.table_name.as_str()))
+        .add_attribute(("billing_mode", billing_mode.as_str()));
+    block = add_keys(block, input.key_schema());
+    block = add_throughput(
+        block,
+        input.provisioned_throughput(),
+        input.on_demand_throughput(),
+        input.warm_throughput(),
+    );
+    if let Some(table_class) = input.table_class() {
+        block = block.add_attribute(("table_class", table_class.as_str()));
+    }
+    if table.deletion_protection {
+        block = block.add_attribute(("deletion_protection_enabled", true));
+    }
+    if let Some(view_type) = input
+        .stream_specification()
+        .and_then(|s| s.stream_view_type())
+    {
+        block = block
+            .add_attribute(("stream_enabled", true))
+            .add_attribute(("stream_view_type", view_type.as_str()));
+    }
+
+    for attr in input.attribute_definitions() {
+        block = block.add_block(
+            Block::builder("attribute")
+                .add_attribute(("name", attr.attribute_name()))
+                .add_attribute(("type", attr.attribute_type().as_str()))
+                .build(),
+        );
+    }
+    for gsi in input.global_secondary_indexes() {
+        let index =
+            Block::builder("global_secondary_index").add_attribute(("name", gsi.index_name()));
+        let index = add_projection(add_keys(index, gsi.key_schema()), gsi.projection());
+        let index = add_throughput(
+            index,
+            gsi.provisioned_throughput(),
+            gsi.on_demand_throughput(),
+            gsi.warm_throughput(),
+        );
+        block = block.add_block(index.build());
+    }
+    for lsi in input.local_secondary_indexes() {
+        // The partition key of an LSI is always the table's own
+        let range_key = lsi
+            .key_schema()
+            .iter()
+            .filter(|k| k.key_type() == &KeyType::Range)
+            .map(|k| k.attribute_name());
+        let index = Block::builder("local_secondary_index")
+            .add_attribute(("name", lsi.index_name()))
+            .add_attributes(range_key.map(|name| ("range_key", name)));
+        block = block.add_block(add_projection(index, lsi.projection()).build());
+    }
+
+    if let Some(ref attribute) = table.ttl_attribute {
+        block = block.add_block(
+            Block::builder("ttl")
+                .add_attribute(("attribute_name", attribute.as_str()))
+                .add_attribute(("enabled", true))
+                .build(),
+        );
+    }
+    if let Some(sse) = input.sse_specification() {
+        let mut encryption =
+            Block::builder("server_side_encryption").add_attribute(("enabled", true));
+        if let Some(key_id) = sse.kms_master_key_id() {
+            // Terraform only accepts an ARN here, not a key id or alias
+            if !key_id.starts_with("arn:") {
+                return Err(DynamoToolsError::TemplateRender(format!(
+                    "table '{}': Terraform's kms_key_arn needs a KMS key ARN, got '{}'",
+                    table.table_name, key_id
+                )));
+            }
+            encryption = encryption.add_attribute(("kms_key_arn", key_id));
+        }
+        block = block.add_block(encryption.build());
+    }
+    if table.point_in_time_recovery {
+        block = block.add_block(
+            Block::builder("point_in_time_recovery")
+                .add_attribute(("enabled", true))
+                .build(),
+        );
+    }
+    for replica in &table.replicas {
+        block = block.add_block(
+            Block::builder("replica")
+                .add_attribute(("region_name", replica.region.as_str()))
+                .build(),
+        );
+    }
+    let tags = config.table_tags(table);
+    if !tags.is_empty() {
+        let tags: Expression = tags
+            .iter()
+            .map(|(key, value)| (key.as_str(), value.as_str()))
+            .collect();
+        block = block.add_attribute(("tags", tags));
+    }
+    Ok(block.build())
+}
+
+fn add_keys(block: BlockBuilder, elements: &[KeySchemaElement]) -> BlockBuilder {
+    elements.iter().fold(block, |block, k| {
+        let name = match k.key_type() {
+            KeyType::Range => "range_key",
+            _ => "hash_key",
+        };
+        block.add_attribute((name, k.attribute_name()))
+    })
+}
+
+fn add_projection(block: BlockBuilder, projection: Option<&Projection>) -> BlockBuilder {
+    let Some(projection) = projection else {
+        return block;
+    };
+    let mut block = block;
+    if let Some(projection_type) = projection.projection_type() {
+        block = block.add_attribute(("projection_type", projection_type.as_str()));
+    }
+    if !projection.non_key_attributes().is_empty() {
+        block = block.add_attribute(("non_key_attributes", projection.non_key_attributes()));
+    }
+    block
+}
+
+fn add_throughput(
+    mut block: BlockBuilder,
+    provisioned: Option<&ProvisionedThroughput>,
+    on_demand: Option<&OnDemandThroughput>,
+    warm: Option<&SdkWarmThroughput>,
+) -> BlockBuilder {
+    if let Some(pt) = provisioned {
+        block = block
+            .add_attribute(("read_capacity", pt.read_capacity_units()))
+            .add_attribute(("write_capacity", pt.write_capacity_units()));
+    }
+    if let Some(od) = on_demand {
+        block = block.add_block(
+            Block::builder("on_demand_throughput")
+                .add_attributes(
+                    od.max_read_request_units()
+                        .map(|units| ("max_read_request_units", units)),
+                )
+                .add_attributes(
+                    od.max_write_request_units()
+                        .map(|units| ("max_write_request_units", units)),
+                )
+                .build(),
+        );
+    }
+    if let Some(warm) = warm {
+        block = block.add_block(
+            Block::builder("warm_throughput")
+                .add_attributes(
+                    warm.read_units_per_second()
+                        .map(|units| ("read_units_per_second", units)),
+                )
+                .add_attributes(
+                    warm.write_units_per_second()
+                        .map(|units| ("write_units_per_second", units)),
+                )
+                .build(),
+        );
+    }
+    block
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::IndexProjection;
+
+    #[test]
+    fn resource_name_should_be_an_identifier() {
+        assert_eq!(resource_name("users"), "users");
+        assert_eq!(resource_name("user-events.v2"), "user_events_v2");
+        assert_eq!(resource_name("2024_users"), "_2024_users");
+    }
+
+    #[test]
+    fn config_should_render_terraform() {
+        let mut config = TableConfig::load_from_file("fixtures/dev.yml").unwrap();
+        config.tables[0].ttl_attribute = Some("expires_at".to_string());
+        config.tables[0]
+            .tags
+            .insert("created-by".to_string(), "tests".to_string());
+
+        let hcl = config.to_terraform().unwrap();
+        assert!(hcl.contains("resource \"aws_dynamodb_table\" \"users\" {"));
+        assert!(hcl.contains("billing_mode = \"PAY_PER_REQUEST\""));
+        assert!(hcl.contains("hash_key = \"pk\""));
+        assert!(hcl.contains("range_key = \"sk\""));
+        assert!(hcl.contains("non_key_attributes = [\n      \"pk\"\n    ]"));
+
+        // The output must parse back as HCL with the expected structure
+        let body = hcl::parse(&hcl).unwrap();
+        let resource = body.blocks().next().unwrap();
+        let blocks: Vec<&str> = resource.body().blocks().map(|b| b.identifier()).collect();
+        assert_eq!(
+            blocks,
+            vec![
+                "attribute",
+                "attribute",
+                "attribute",
+                "attribute",
+                "attribute",
+                "global_secondary_index",
+                "local_secondary_index",
+                "ttl",
+            ]
+        );
+        assert!(resource.body().attributes().any(|a| a.key() == "tags"));
+    }
+
+    #[test]
+    fn kms_keys_should_be_arns_for_terraform() {
+        let mut config = TableConfig::load_from_file("fixtures/dev.yml").unwrap();
+        config.tables[0].encryption = Some(Encryption {
+            kms_key_id: Some("alias/users".to_string()),
+        });
+        let err = config.to_terraform().unwrap_err();
+        assert!(
+            matches!(err, DynamoToolsError::TemplateRender(ref msg) if msg.contains("'alias/users'"))
+        );
+
+        let arn = "arn:aws:kms:us-east-1:123456789012:key/1234abcd-12ab-34cd-56ef-1234567890ab";
+        config.tables[0].encryption = Some(Encryption {
+            kms_key_id: Some(arn.to_string()),
+        });
+        let hcl = config.to_terraform().unwrap();
+        assert!(hcl.contains(&format!("kms_key_arn = \"{}\"", arn)));
+    }
+
+    #[test]
+    fn exported_terraform_should_import_back() {
+        let mut config = TableConfig::load_from_file("fixtures/dev.yml").unwrap();
+        config.tables[0].ttl_attribute = Some("expires_at".to_string());
+        config.tables[0].point_in_time_recovery = true;
+        config.tables[0].gsis[0].throughput = Some(Throughput::OnDemand {
+            max_read: Some(10),
+            max_write: None,
+        });
+        let hcl = config.to_terraform().unwrap();
+
+        let tables = TableInfo::from_terraform(&hcl).unwrap();
+        assert_eq!(tables.len(), 1);
+        let info = &tables[0];
+        assert_eq!(info.table_name, "users");
+        assert_eq!(info.key_names(), vec!["pk".to_string(), "sk".to_string()]);
+        assert_eq!(info.gsis[0].projection, Some(IndexProjection::Include));
+        assert_eq!(
+            info.gsis[0].throughput,
+            Some(Throughput::OnDemand {
+                max_read: Some(10),
+                max_write: None
+            })
+        );
+        assert_eq!(info.lsis[0].projection, Some(IndexProjection::All));
+        assert_eq!(info.ttl_attribute.as_deref(), Some("expires_at"));
+        assert!(info.point_in_time_recovery);
+
+        let reimported = TableConfig { tables, ..config };
+        assert_eq!(reimported.to_terraform().unwrap(), hcl);
+    }
+
+    #[test]
+    fn non_literal_values_should_be_ignored_on_import() {
+        let hcl = r#"
+variable "env" {}
+
+resource "aws_dynamodb_table" "orders" {
+  name           = "${var.env}-orders"
+  billing_mode   = "PROVISIONED"
+  read_capacity  = 10
+  write_capacity = 5
+  hash_key       = "id"
+
+  attribute {
+    name = "id"
+    type = "N"
+  }
+
+  replica {
+    region_name = "eu-west-1"
+  }
+
+  tags = {
+    env  = var.env
+    team = "orders"
+  }
+}
+"#;
+        let tables = TableInfo::from_terraform(hcl).unwrap();
+        assert_eq!(tables.len(), 1);
+        let info = &tables[0];
+        assert_eq!(info.table_name, "orders");
+        assert_eq!(info.pk.attr_type, AttrType::N);
+        assert_eq!(
+            info.throughput,
+            Some(Throughput::Provisioned { read: 10, write: 5 })
+        );
+        assert_eq!(info.replicas[0].region, "eu-west-1");
+        assert_eq!(
+            info.tags,
+            BTreeMap::from([("team".to_string(), "orders".to_string())])
+        );
+
+        let hcl = hcl.replace("= 10", "= var.read_capacity");
+        let err = TableInfo::from_terraform(&hcl).unwrap_err();
+        assert!(matches!(
+            err,
+            DynamoToolsError::TemplateParse(ref msg)
+                if msg.contains("read_capacity must be") && msg.contains("var.read_capacity")
+        ));
+
+        let hcl = hcl.replace("= var.read_capacity", "= 10").replace(
+            "  replica {",
+            "  server_side_encryption {\n    enabled     = true\n    kms_key_arn = aws_kms_key.orders.arn\n  }\n\n  replica {",
+        );
+        let err = TableInfo::from_terraform(&hcl).unwrap_err();
+        assert!(matches!(
+            err,
+            DynamoToolsError::TemplateParse(ref msg)
+                if msg.contains("kms_key_arn must be") && msg.contains("aws_kms_key.orders.arn")
+        ));
+    }
+}
@@ -0,0 +1,709 @@
+use crate::config::defined_attr;
+use crate::error::{DynamoToolsError, Result};
+use crate::item::{self, Item, item_from_dynamodb_json, item_to_dynamodb_json};
+use crate::{
+    AttrType, AutoScaling, IndexProjection, ScalingSettings, SeedMode, TableAttr, TableConfig,
+    TableGsi, TableInfo, Throughput,
+};
+use aws_sdk_dynamodb::types::AttributeValue;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use std::collections::hash_map::Entry;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::Path;
+
+/// A NoSQL Workbench data model: tables with their facets and sample data.
+///
+/// Load a model exported from NoSQL Workbench with [`WorkbenchModel::load`] and
+/// create seeded tables from it with `DynamodbConnector::try_from_workbench`, or
+/// build one from a [`TableConfig`] with [`WorkbenchModel::from_config`] and
+/// [`WorkbenchModel::save`] it for NoSQL Workbench to import.
+#[derive(Debug, Clone)]
+pub struct WorkbenchModel {
+    /// The name of the model.
+    pub name: String,
+    /// The tables of the model.
+    pub tables: Vec<WorkbenchTable>,
+}
+
+/// A table of a [`WorkbenchModel`].
+#[derive(Debug, Clone)]
+pub struct WorkbenchTable {
+    /// The table schema. Its `table_name` is the base name of the table.
+    pub info: TableInfo,
+    /// The facets (entity views) defined on the table.
+    pub facets: Vec<WorkbenchFacet>,
+    /// Sample data of the table, including the data of its facets.
+    pub items: Vec<Item>,
+}
+
+/// A facet: a named view of the items of one entity type in a table.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WorkbenchFacet {
+    /// The name of the facet, e.g. `Order`.
+    pub name: String,
+    /// Alias of the partition key for this entity type, e.g. `UserId`.
+    pub partition_key_alias: Option<String>,
+    /// Alias of the sort key for this entity type, e.g. `OrderId`.
+    pub sort_key_alias: Option<String>,
+    /// Non-key attributes of this entity type.
+    pub non_key_attributes: Vec<String>,
+}
+
+impl WorkbenchModel {
+    /// Loads a data model exported from NoSQL Workbench.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if the file cannot be read ([`DynamoToolsError::ConfigRead`])
+    /// or is not a valid data model ([`DynamoToolsError::TemplateParse`]).
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let content = fs::read_to_string(path)
+            .map_err(|e| DynamoToolsError::ConfigRead(path.to_string_lossy().to_string(), e))?;
+        Self::parse(&content)
+    }
+
+    /// Parses a NoSQL Workbench data model from its JSON text.
+    ///
+    /// Facet sample data is merged into the sample data of the table; an item whose
+    /// primary key was already seen replaces the earlier one, as a put would. The
+    /// billing mode, provisioned capacity and auto scaling settings are imported too.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` ([`DynamoToolsError::TemplateParse`]) if the JSON is not a valid
+    /// data model, or [`DynamoToolsError::InvalidDynamoDbJson`] for malformed sample data.
+    pub fn parse(json: &str) -> Result<Self> {
+        let model: ModelFile = serde_json::from_str(json)
+            .map_err(|e| DynamoToolsError::TemplateParse(e.to_string()))?;
+        Ok(Self {
+            name: model.model_name,
+            tables: model
+                .data_model
+                .into_iter()
+                .map(import_table)
+                .collect::<Result<_>>()?,
+        })
+    }
+
+    /// Builds a model from the tables of `config`, with the items of each table's
+    /// `seed_data_file` (if any) as sample data.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if a seed data file cannot be read or parsed.
+    pub fn from_config(name: impl Into<String>, config: &TableConfig) -> Result<Self> {
+        let tables = config
+            .tables
+            .iter()
+            .map(|info| {
+                let items = match info.seed_data_file {
+                    Some(ref path) => {
+                        let content = fs::read_to_string(path)
+                            .map_err(|e| DynamoToolsError::SeedFileRead(path.clone(), e))?;
+                        item::parse_items(&content, path)?
+                    }
+                    None => Vec::new(),
+                };
+                Ok(WorkbenchTable {
+                    info: info.clone(),
+                    facets: Vec::new(),
+                    items,
+                })
+            })
+            .collect::<Result<_>>()?;
+        Ok(Self {
+            name: name.into(),
+            tables,
+        })
+    }
+
+    /// Returns the schemas of all tables of the model.
+    pub fn table_infos(&self) -> Vec<TableInfo> {
+        self.tables.iter().map(|t| t.info.clone()).collect()
+    }
+
+    /// Renders the model in the NoSQL Workbench JSON format.
+    ///
+    /// The format only holds the key schema, GSI keys and projections, and the
+    /// table's provisioned capacity and auto scaling. A warning lists every other
+    /// setting a table loses in the export (LSIs, TTL, streams, encryption, deletion
+    /// protection, point-in-time recovery, table class, tags, replicas, on-demand
+    /// and warm throughput, GSI capacity settings and the seed mode).
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` ([`DynamoToolsError::TemplateRender`]) if rendering fails.
+    pub fn to_json(&self) -> Result<String> {
+        let model = ModelFile {
+            model_name: self.name.clone(),
+            model_metadata: Some(json!({
+                "Author": "",
+                "DateCreated": "",
+                "DateLastModified": "",
+                "Description": "",
+                "AWSService": "Amazon DynamoDB",
+                "Version": "3.0",
+            })),
+            data_model: self
+                .tables
+                .iter()
+                .map(export_table)
+                .collect::<Result<_>>()?,
+        };
+        serde_json::to_string_pretty(&model)
+            .map_err(|e| DynamoToolsError::TemplateRender(e.to_string()))
+    }
+
+    /// Writes the model in the NoSQL Workbench JSON format to `path`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if rendering fails or the file cannot be written
+    /// ([`DynamoToolsError::ExportFileWrite`]).
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        fs::write(path, self.to_json()?)
+            .map_err(|e| DynamoToolsError::ExportFileWrite(path.to_string_lossy().to_string(), e))
+    }
+}
+
+fn import_table(table: TableModel) -> Result<WorkbenchTable> {
+    let mut defs = BTreeMap::new();
+    let key_attrs = [&table.key_attributes]
+        .into_iter()
+        .chain(
+            table
+                .global_secondary_indexes
+                .iter()
+                .map(|gsi| &gsi.key_attributes),
+        )
+        .flat_map(|keys| [Some(&keys.partition_key), keys.sort_key.as_ref()])
+        .flatten();
+    for attr in key_attrs {
+        defs.insert(
+            attr.attribute_name.clone(),
+            AttrType::parse(&attr.attribute_type)?,
+        );
+    }
+    let key = |attr: &AttributeModel| defined_attr(&defs, &attr.attribute_name);
+
+    let mut info = TableInfo::new(
+        &table.table_name,
+        key(&table.key_attributes.partition_key)?,
+        table
+            .key_attributes
+            .sort_key
+            .as_ref()
+            .map(key)
+            .transpose()?,
+    );
+    for gsi in &table.global_secondary_indexes {
+        info.gsis.push(TableGsi {
+            name: gsi.index_name.clone(),
+            pk: key(&gsi.key_attributes.partition_key)?,
+            sk: gsi.key_attributes.sort_key.as_ref().map(key).transpose()?,
+            attrs: gsi.projection.non_key_attributes.clone(),
+            projection: Some(gsi.projection.projection_type),
+            throughput: None,
+            warm_throughput: None,
+            auto_scaling: None,
+        });
+    }
+    let key_names = info.key_names();
+    info.attrs = defs
+        .into_iter()
+        .filter(|(name, _)| !key_names.contains(name))
+        .map(|(name, attr_type)| TableAttr { name, attr_type })
+        .collect();
+
+    if let Some(capacity) = table.provisioned_capacity_settings
+        && table.billing_mode.as_deref() != Some("PAY_PER_REQUEST")
+    {
+        if let Some(pt) = capacity.provisioned_throughput {
+            info.throughput = Some(Throughput::Provisioned {
+                read: pt.read_capacity_units,
+                write: pt.write_capacity_units,
+            });
+            // Indexes share the table's capacity settings in the model
+            for gsi in &mut info.gsis {
+                gsi.throughput = info.throughput.clone();
+            }
+        }
+        let read = capacity.auto_scaling_read.map(ScalingSettings::from);
+        let write = capacity.auto_scaling_write.map(ScalingSettings::from);
+        if read.is_some() || write.is_some() {
+            info.auto_scaling = Some(AutoScaling { read, write });
+        }
+    }
+
+    // Facets usually repeat items of the table data, keep one item per primary key
+    let key_names = info.key_names();
+    let mut items: Vec<Item> = Vec::new();
+    let mut positions = HashMap::new();
+    let facet_data = table.table_facets.iter().flat_map(|f| f.table_data.iter());
+    for value in table.table_data.iter().chain(facet_data) {
+        let item = item_from_dynamodb_json(value.clone())?;
+        match positions.entry(item::key_id(&item, &key_names)?) {
+            Entry::Occupied(entry) => items[*entry.get()] = item,
+            Entry::Vacant(entry) => {
+                entry.insert(items.len());
+                items.push(item);
+            }
+        }
+    }
+    let facets = table
+        .table_facets
+        .into_iter()
+        .map(|facet| WorkbenchFacet {
+            name: facet.facet_name,
+            partition_key_alias: facet
+                .key_attribute_alias
+                .as_ref()
+                .and_then(|a| a.partition_key_alias.clone()),
+            sort_key_alias: facet.key_attribute_alias.and_then(|a| a.sort_key_alias),
+            non_key_attributes: facet.non_key_attributes,
+        })
+        .collect();
+    Ok(WorkbenchTable {
+        info,
+        facets,
+        items,
+    })
+}
+
+fn export_table(table: &WorkbenchTable) -> Result<TableModel> {
+    let info = &table.info;
+    let dropped = dropped_settings(info);
+    if !dropped.is_empty() {
+        eprintln!(
+            "[WARN] NoSQL Workbench models have no {} settings, dropped from table '{}'",
+            dropped.join(", "),
+            info.table_name
+        );
+    }
+    let attribute = |attr: &TableAttr| AttributeModel {
+        attribute_name: attr.name.clone(),
+        attribute_type: format!("{:?}", attr.attr_type),
+    };
+
+    // Non-key attributes: configured ones first, then any other seen in the items
+    let key_names = info.key_names();
+    let mut non_key: BTreeMap<String, String> = BTreeMap::new();
+    let index_keys = info
+        .gsis
+        .iter()
+        .flat_map(|gsi| [Some(&gsi.pk), gsi.sk.as_ref()])
+        .flatten();
+    for attr in info.attrs.iter().chain(index_keys) {
+        non_key.insert(attr.name.clone(), format!("{:?}", attr.attr_type));
+    }
+    for item in &table.items {
+        for (name, value) in item {
+            non_key
+                .entry(name.clone())
+                .or_insert_with(|| attribute_type(value).to_string());
+        }
+    }
+    let non_key_attributes = non_key
+        .into_iter()
+        .filter(|(name, _)| !key_names.contains(name))
+        .map(|(attribute_name, attribute_type)| AttributeModel {
+            attribute_name,
+            attribute_type,
+        })
+        .collect();
+
+    let (billing_mode, provisioned_capacity_settings) = match info.throughput {
+        Some(Throughput::Provisioned { read, write }) => (
+            "PROVISIONED",
+            Some(CapacitySettings {
+                provisioned_throughput: Some(ProvisionedModel {
+                    read_capacity_units: read,
+                    write_capacity_units: write,
+                }),
+                auto_scaling_read: info
+                    .auto_scaling
+                    .as_ref()
+                    .and_then(|a| a.read.clone())
+                    .map(Into::into),
+                auto_scaling_write: info
+                    .auto_scaling
+                    .as_ref()
+                    .and_then(|a| a.write.clone())
+                    .map(Into::into),
+            }),
+        ),
+        _ => ("PAY_PER_REQUEST", None),
+    };
+
+    Ok(TableModel {
+        table_name: info.table_name.clone(),
+        key_attributes: KeyAttributes {
+            partition_key: attribute(&info.pk),
+            sort_key: info.sk.as_ref().map(attribute),
+        },
+        non_key_attributes,
+        table_facets: table
+            .facets
+            .iter()
+            .map(|facet| FacetModel {
+                facet_name: facet.name.clone(),
+                key_attribute_alias: Some(KeyAlias {
+                    partition_key_alias: facet.partition_key_alias.clone(),
+                    sort_key_alias: facet.sort_key_alias.clone(),
+                }),
+                table_data: Vec::new(),
+                non_key_attributes: facet.non_key_attributes.clone(),
+                data_access: Some(json!({ "MySql": {} })),
+            })
+            .collect(),
+        global_secondary_indexes: info
+            .gsis
+            .iter()
+            .map(|gsi| {
+                let projection_type = gsi.projection.unwrap_or(IndexProjection::Include);
+                GsiModel {
+                    index_name: gsi.name.clone(),
+                    key_attributes: KeyAttributes {
+                        partition_key: attribute(&gsi.pk),
+                        sort_key: gsi.sk.as_ref().map(attribute),
+                    },
+                    projection: ProjectionModel {
+                        projection_type,
+                        non_key_attributes: match projection_type {
+                            IndexProjection::Include => gsi.attrs.clone(),
+                            _ => Vec::new(),
+                        },
+                    },
+                }
+            })
+            .collect(),
+        table_data: table
+            .items
+            .iter()
+            .map(item_to_dynamodb_json)
+            .collect::<Result<_>>()?,
+        data_access: Some(json!({ "MySql": {} })),
+        billing_mode: Some(billing_mode.to_string()),
+        provisioned_capacity_settings,
+    })
+}
+
+// The settings of `info` a NoSQL Workbench model cannot hold. Models only keep the
+// key schema, GSI keys and projections, and the table's provisioned capacity and
+// auto scaling (which GSIs share on import)
+fn dropped_settings(info: &TableInfo) -> Vec<&'static str> {
+    let provisioned = matches!(info.throughput, Some(Throughput::Provisioned { .. }));
+    [
+        (!info.lsis.is_empty(), "local secondary indexes"),
+        (info.ttl_attribute.is_some(), "TTL"),
+        (info.stream_view_type.is_some(), "stream"),
+        (info.encryption.is_some(), "encryption"),
+        (info.deletion_protection, "deletion protection"),
+        (info.point_in_time_recovery, "point-in-time recovery"),
+        (info.table_class.is_some(), "table class"),
+        (!info.tags.is_empty(), "tags"),
+        (!info.replicas.is_empty(), "replicas"),
+        (info.warm_throughput.is_some(), "warm throughput"),
+        (
+            matches!(info.throughput, Some(Throughput::OnDemand { .. })),
+            "on-demand throughput",
+        ),
+        (
+            !provisioned && info.auto_scaling.is_some(),
+            "auto scaling without provisioned throughput",
+        ),
+        (
+            info.gsis
+                .iter()
+                .any(|gsi| gsi.throughput.is_some() && gsi.throughput != info.throughput),
+            "GSI throughput",
+        ),
+        (
+            info.gsis.iter().any(|gsi| gsi.warm_throughput.is_some()),
+            "GSI warm throughput",
+        ),
+        (
+            info.gsis.iter().any(|gsi| gsi.auto_scaling.is_some()),
+            "GSI auto scaling",
+        ),
+        (info.seed_mode != SeedMode::default(), "seed mode"),
+    ]
+    .into_iter()
+    .filter_map(|(set, setting)| set.then_some(setting))
+    .collect()
+}
+
+// The DynamoDB JSON type tag of a value, as used for NoSQL Workbench attribute types
+fn attribute_type(value: &AttributeValue) -> &'static str {
+    match value {
+        AttributeValue::S(_) => "S",
+        AttributeValue::N(_) => "N",
+        AttributeValue::B(_) => "B",
+        AttributeValue::Bool(_) => "BOOL",
+        AttributeValue::Ss(_) => "SS",
+        AttributeValue::Ns(_) => "NS",
+        AttributeValue::Bs(_) => "BS",
+        AttributeValue::L(_) => "L",
+        AttributeValue::M(_) => "M",
+        _ => "NULL",
+    }
+}
+
+impl From<AutoScalingModel> for ScalingSettings {
+    fn from(model: AutoScalingModel) -> Self {
+        Self {
+            min: model.scalable_target_request.min_capacity,
+            max: model.scalable_target_request.max_capacity,
+            target_utilization: model.scaling_policy_configuration.target_value,
+        }
+    }
+}
+
+impl From<ScalingSettings> for AutoScalingModel {
+    fn from(settings: ScalingSettings) -> Self {
+        Self {
+            scalable_target_request: ScalableTargetModel {
+                min_capacity: settings.min,
+                max_capacity: settings.max,
+                service_role: Some(
+                    "AWSServiceRoleForApplicationAutoScaling_DynamoDBTable".to_string(),
+                ),
+            },
+            scaling_policy_configuration: ScalingPolicyModel {
+                target_value: settings.target_utilization,
+            },
+        }
+    }
+}
+
+// --- The NoSQL Workbench JSON format ---
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct ModelFile {
+    model_name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    model_metadata: Option<Value>,
+    data_model: Vec<TableModel>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct TableModel {
+    table_name: String,
+    key_attributes: KeyAttributes,
+    #[serde(default)]
+    non_key_attributes: Vec<AttributeModel>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    table_facets: Vec<FacetModel>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    global_secondary_indexes: Vec<GsiModel>,
+    #[serde(default)]
+    table_data: Vec<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    data_access: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    billing_mode: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    provisioned_capacity_settings: Option<CapacitySettings>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct KeyAttributes {
+    partition_key: AttributeModel,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    sort_key: Option<AttributeModel>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct AttributeModel {
+    attribute_name: String,
+    attribute_type: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct FacetModel {
+    facet_name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    key_attribute_alias: Option<KeyAlias>,
+    #[serde(default)]
+    table_data: Vec<Value>,
+    #[serde(default)]
+    non_key_attributes: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    data_access: Option<Value>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct KeyAlias {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    partition_key_alias: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    sort_key_alias: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct GsiModel {
+    index_name: String,
+    key_attributes: KeyAttributes,
+    projection: ProjectionModel,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct ProjectionModel {
+    projection_type: IndexProjection,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    non_key_attributes: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct CapacitySettings {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    provisioned_throughput: Option<ProvisionedModel>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    auto_scaling_read: Option<AutoScalingModel>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    auto_scaling_write: Option<AutoScalingModel>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct ProvisionedModel {
+    read_capacity_units: i64,
+    write_capacity_units: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct AutoScalingModel {
+    scalable_target_request: ScalableTargetModel,
+    scaling_policy_configuration: ScalingPolicyModel,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct ScalableTargetModel {
+    min_capacity: i32,
+    max_capacity: i32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    service_role: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct ScalingPolicyModel {
+    target_value: f64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn workbench_model_could_be_loaded() {
+        let model = WorkbenchModel::load("fixtures/workbench_model.json").unwrap();
+        assert_eq!(model.name, "Shop");
+        assert_eq!(model.tables.len(), 1);
+
+        let table = &model.tables[0];
+        let info = &table.info;
+        assert_eq!(info.table_name, "shop");
+        assert_eq!(info.key_names(), vec!["pk".to_string(), "sk".to_string()]);
+        assert_eq!(info.gsis[0].name, "gsi1");
+        assert_eq!(info.gsis[0].projection, Some(IndexProjection::All));
+        assert_eq!(info.attrs.len(), 1);
+        assert_eq!(
+            info.throughput,
+            Some(Throughput::Provisioned { read: 5, write: 5 })
+        );
+        assert_eq!(
+            info.auto_scaling.as_ref().and_then(|a| a.read.as_ref()),
+            Some(&ScalingSettings {
+                min: 1,
+                max: 10,
+                target_utilization: 70.0
+            })
+        );
+
+        assert_eq!(table.facets.len(), 2);
+        assert_eq!(table.facets[1].name, "Order");
+        assert_eq!(table.facets[1].sort_key_alias.as_deref(), Some("OrderId"));
+        // Two table items plus one new facet item, the User facet repeats the profile
+        assert_eq!(table.items.len(), 3);
+        assert_eq!(
+            table.items[0]["name"],
+            AttributeValue::S("Alice".to_string())
+        );
+    }
+
+    #[test]
+    fn workbench_model_should_round_trip() {
+        let model = WorkbenchModel::load("fixtures/workbench_model.json").unwrap();
+        let json = model.to_json().unwrap();
+        let reloaded = WorkbenchModel::parse(&json).unwrap();
+        assert_eq!(reloaded.to_json().unwrap(), json);
+        assert_eq!(reloaded.tables[0].items, model.tables[0].items);
+        assert_eq!(reloaded.tables[0].facets, model.tables[0].facets);
+
+        let value: Value = serde_json::from_str(&json).unwrap();
+        let attrs = &value["DataModel"][0]["NonKeyAttributes"];
+        assert_eq!(
+            *attrs,
+            json!([
+                { "AttributeName": "gsi1pk", "AttributeType": "S" },
+                { "AttributeName": "name", "AttributeType": "S" },
+                { "AttributeName": "total", "AttributeType": "N" },
+            ])
+        );
+    }
+
+    #[test]
+    fn workbench_model_could_be_built_from_config() {
+        let config = TableConfig::load_from_file("fixtures/dev.yml").unwrap();
+        let model = WorkbenchModel::from_config("dev", &config).unwrap();
+        assert_eq!(model.tables[0].items.len(), 3);
+
+        let reloaded = WorkbenchModel::parse(&model.to_json().unwrap()).unwrap();
+        let info = &reloaded.tables[0].info;
+        assert_eq!(info.table_name, "users");
+        assert_eq!(info.gsis[0].attrs, vec!["pk".to_string()]);
+        assert_eq!(info.throughput, None);
+        assert_eq!(reloaded.tables[0].items, model.tables[0].items);
+    }
+
+    #[test]
+    fn dropped_settings_should_be_listed() {
+        let model = WorkbenchModel::load("fixtures/workbench_model.json").unwrap();
+        let mut info = model.tables[0].info.clone();
+        assert!(dropped_settings(&info).is_empty());
+
+        info.deletion_protection = true;
+        info.tags.insert("team".to_string(), "shop".to_string());
+        info.gsis[0].throughput = Some(Throughput::Provisioned { read: 1, write: 1 });
+        info.throughput = Some(Throughput::OnDemand {
+            max_read: Some(10),
+            max_write: None,
+        });
+        assert_eq!(
+            dropped_settings(&info),
+            vec![
+                "deletion protection",
+                "tags",
+                "on-demand throughput",
+                "auto scaling without provisioned throughput",
+                "GSI throughput",
+            ]
+        );
+    }
+}
@@ -0,0 +1,39 @@
+// tests/codegen_test.rs
+use aws_sdk_dynamodb::types::AttributeValue;
+use dynamodb_tools::TableConfig;
+
+// Generated from fixtures/codegen.yml, as a build script would
+#[allow(dead_code)]
+mod tables {
+    include!("../fixtures/codegen_tables.rs");
+}
+
+#[test]
+fn generated_code_should_match_fixture() {
+    let config = TableConfig::load_from_file("fixtures/codegen.yml").unwrap();
+    let expected = std::fs::read_to_string("fixtures/codegen_tables.rs").unwrap();
+    assert_eq!(config.to_rust().unwrap(), expected);
+}
+
+#[test]
+fn generated_keys_should_convert_to_items() {
+    use tables::order_events::{ByDigestKey, Key, attrs, indexes};
+
+    assert_eq!(tables::order_events::TABLE_NAME, "order-events");
+    assert_eq!(tables::users::attrs::EMAIL, "email");
+    assert_eq!(indexes::BY_TYPE, "by_type");
+    assert_eq!(attrs::TYPE, "type");
+    assert_eq!(attrs::EXPIRES_AT, "expires_at");
+
+    let key = Key {
+        order_id: "o-1".to_string(),
+        seq: 7,
+    }
+    .to_key();
+    assert_eq!(key["orderId"], AttributeValue::S("o-1".to_string()));
+    assert_eq!(key["seq"], AttributeValue::N("7".to_string()));
+
+    let key = ByDigestKey { digest: vec![1, 2] }.to_key();
+    assert_eq!(key.len(), 1);
+    assert!(matches!(key[attrs::DIGEST], AttributeValue::B(ref b) if b.as_ref() == [1, 2]));
+}
@@ -2,8 +2,11 @@
 #![cfg(feature = "test_utils")]
 use aws_sdk_dynamodb::types::AttributeValue;
 #[cfg(feature = "test_utils")]
-use dynamodb_tools::{AttrType, DynamoToolsError, TableInfo};
-use dynamodb_tools::{DynamodbConnector, Result, TableConfig};
+use dynamodb_tools::{AttrType, DataFormat, DynamoToolsError, SeedMode, TableClass, TableInfo};
+use dynamodb_tools::{
+    CopyOptions, DynamodbConnector, Result, Snapshot, SweepTarget, TableConfig, WorkbenchModel,
+    copy_table, sweep_orphans,
+};
 use std::collections::HashMap;
 
 // Note: Assumes DynamoDB Local is running at http://localhost:8000
@@ -65,7 +68,18 @@ async fn simple_pk_table_should_allow_put() -> Result<()> {
         gsis: vec![],
         lsis: vec![],
         throughput: None,
+        warm_throughput: None,
+        auto_scaling: None,
         seed_data_file: None,
+        seed_mode: SeedMode::default(),
+        tags: Default::default(),
+        encryption: None,
+        table_class: None,
+        deletion_protection: false,
+        point_in_time_recovery: false,
+        replicas: vec![],
+        ttl_attribute: None,
+        stream_view_type: None,
     };
 
     // Create TableConfig with a list containing the single table info
@@ -74,6 +88,8 @@ async fn simple_pk_table_should_allow_put() -> Result<()> {
         endpoint: Some("http://localhost:8000".to_string()),
         delete_on_exit: true,
         tables: vec![table_info],
+        sweep_orphans_older_than: None,
+        tags: Default::default(),
     };
 
     let connector = DynamodbConnector::try_new(config).await?;
@@ -195,3 +211,688 @@ async fn dev_config_should_seed_data() -> Result<()> {
 
     Ok(())
 }
+
+#[cfg(feature = "test_utils")]
+#[tokio::test]
+async fn all_or_nothing_seed_mode_should_write_all_items() -> Result<()> {
+    let mut config = TableConfig::load_from_file("fixtures/dev.yml")?;
+    config.tables[0].seed_mode = SeedMode::AllOrNothing;
+    let connector = DynamodbConnector::try_new(config).await?;
+
+    let table_name = connector.get_created_table_name("users").unwrap();
+    let resp = connector
+        .client()?
+        .scan()
+        .table_name(table_name)
+        .send()
+        .await
+        .map_err(|e| DynamoToolsError::Internal(format!("Scan failed: {}", e)))?;
+
+    assert_eq!(resp.count(), 3);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn all_or_nothing_seed_mode_should_keep_existing_items() -> Result<()> {
+    let mut config = TableConfig::load_from_file("fixtures/dev.yml")?;
+    config.tables[0].seed_data_file = None;
+    config.tables[0].seed_mode = SeedMode::AllOrNothing;
+    let connector = DynamodbConnector::try_new(config).await?;
+    let users = connector.table("users")?;
+    let existing = serde_json::json!({ "pk": "user_2", "sk": "profile", "name": "Existing" });
+    users.put(&existing).await?;
+
+    // user_2/profile is also in the seed file, so the seed fails as a whole
+    let err = connector
+        .seed("users", "fixtures/seed_users.json")
+        .await
+        .unwrap_err();
+    assert!(matches!(err, DynamoToolsError::SeedTransactWrite(..)));
+    let items: Vec<serde_json::Value> = users.scan().await?;
+    assert_eq!(items, vec![existing]);
+
+    Ok(())
+}
+
+#[cfg(feature = "test_utils")]
+#[tokio::test]
+async fn seed_items_should_load_fixtures_into_created_table() -> Result<()> {
+    let config = TableConfig::load_from_file("fixtures/multi_table.yml")?;
+    let connector = DynamodbConnector::try_new(config).await?;
+
+    connector
+        .seed_items(
+            "multi_table_2",
+            vec![
+                serde_json::json!({ "id2": 1, "sort_key": "a", "data": "first" }),
+                serde_json::json!({ "id2": 2, "sort_key": "b", "data": "second" }),
+            ],
+        )
+        .await?;
+    connector
+        .seed("multi_table_1", "fixtures/seed_multi_table_1.json")
+        .await?;
+
+    for (base_name, expected) in [("multi_table_1", 2), ("multi_table_2", 2)] {
+        let resp = connector
+            .client()?
+            .scan()
+            .table_name(connector.get_created_table_name(base_name).unwrap())
+            .send()
+            .await
+            .map_err(|e| DynamoToolsError::Internal(format!("Scan failed: {}", e)))?;
+        assert_eq!(resp.count(), expected);
+    }
+
+    let err = connector
+        .seed_items("unknown", Vec::<serde_json::Value>::new())
+        .await
+        .unwrap_err();
+    assert!(matches!(err, DynamoToolsError::UnknownTable(name) if name == "unknown"));
+
+    Ok(())
+}
+
+#[cfg(feature = "test_utils")]
+#[tokio::test]
+async fn exported_table_should_seed_back() -> Result<()> {
+    let config = TableConfig::load_from_file("fixtures/dev.yml")?;
+    let source = DynamodbConnector::try_new(config.clone()).await?;
+
+    let mut empty_config = config;
+    empty_config.tables[0].seed_data_file = None;
+    let dest = DynamodbConnector::try_new(empty_config).await?;
+
+    for (format, segments) in [
+        (DataFormat::Json, 1),
+        (DataFormat::NdJson, 2),
+        (DataFormat::DynamoDbJson, 3),
+    ] {
+        let path = std::env::temp_dir().join(format!("users-{}.export", unique_suffix()));
+        let count = source
+            .export_table_segmented("users", &path, format, segments)
+            .await?;
+        assert_eq!(count, 3);
+
+        dest.seed("users", &path).await?;
+        let real_name = dest.get_created_table_name("users").unwrap();
+        let count = dest
+            .export_table(real_name, &path, DataFormat::Json)
+            .await?;
+        assert_eq!(count, 3);
+        std::fs::remove_file(&path).ok();
+    }
+
+    Ok(())
+}
+
+fn unique_suffix() -> String {
+    format!(
+        "{}-{:?}",
+        std::process::id(),
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_nanos()
+    )
+}
+
+#[cfg(feature = "test_utils")]
+#[tokio::test]
+async fn truncate_and_reset_should_restore_seeded_state() -> Result<()> {
+    let config = TableConfig::load_from_file("fixtures/dev.yml")?;
+    let connector = DynamodbConnector::try_new(config).await?;
+
+    connector
+        .seed_items(
+            "users",
+            vec![serde_json::json!({ "pk": "user_3", "sk": "profile", "name": "Carol" })],
+        )
+        .await?;
+
+    connector.reset("users").await?;
+    assert_eq!(count_items(&connector, "users").await?, 3);
+
+    assert_eq!(connector.truncate("users").await?, 3);
+    assert_eq!(connector.truncate("users").await?, 0);
+
+    Ok(())
+}
+
+async fn count_items(connector: &DynamodbConnector, base_name: &str) -> Result<i32> {
+    let resp = connector
+        .client()?
+        .scan()
+        .table_name(connector.get_created_table_name(base_name).unwrap())
+        .select(aws_sdk_dynamodb::types::Select::Count)
+        .send()
+        .await
+        .map_err(|e| DynamoToolsError::Internal(format!("Scan failed: {}", e)))?;
+    Ok(resp.count())
+}
+
+#[cfg(feature = "test_utils")]
+#[tokio::test]
+async fn restore_should_revert_table_to_snapshot() -> Result<()> {
+    let config = TableConfig::load_from_file("fixtures/dev.yml")?;
+    let connector = DynamodbConnector::try_new(config).await?;
+
+    let snapshot = connector.snapshot("users").await?;
+    assert_eq!(snapshot.items().len(), 3);
+
+    connector
+        .seed_items(
+            "users",
+            vec![
+                serde_json::json!({ "pk": "user_1", "sk": "profile", "name": "Changed" }),
+                serde_json::json!({ "pk": "user_3", "sk": "profile", "name": "Carol" }),
+            ],
+        )
+        .await?;
+    assert_eq!(count_items(&connector, "users").await?, 4);
+
+    let path = std::env::temp_dir().join(format!("users-{}.snapshot", unique_suffix()));
+    snapshot.save(&path)?;
+    let loaded = Snapshot::load("users", &path)?;
+    std::fs::remove_file(&path).ok();
+
+    connector.restore(&loaded).await?;
+    let restored = connector.snapshot("users").await?;
+    assert_eq!(restored.items().len(), 3);
+    for item in snapshot.items() {
+        assert!(restored.items().contains(item));
+    }
+
+    Ok(())
+}
+
+#[cfg(feature = "test_utils")]
+#[tokio::test]
+async fn copy_table_should_filter_and_remap_keys() -> Result<()> {
+    let source = DynamodbConnector::load("fixtures/dev.yml").await?;
+    let dest = DynamodbConnector::load("fixtures/multi_table.yml").await?;
+
+    let options = CopyOptions {
+        filter_expression: Some("sk = :sk".to_string()),
+        expression_attribute_values: HashMap::from([(
+            ":sk".to_string(),
+            AttributeValue::S("profile".to_string()),
+        )]),
+        attribute_mapping: HashMap::from([("pk".to_string(), "id1".to_string())]),
+        ..Default::default()
+    };
+    let copied = copy_table(
+        source.client()?,
+        source.get_created_table_name("users").unwrap(),
+        dest.client()?,
+        dest.get_created_table_name("multi_table_1").unwrap(),
+        &options,
+    )
+    .await?;
+    assert_eq!(copied, 2);
+    assert_eq!(count_items(&dest, "multi_table_1").await?, 2);
+
+    let limited = CopyOptions {
+        limit: Some(1),
+        attribute_mapping: HashMap::from([("pk".to_string(), "id1".to_string())]),
+        ..Default::default()
+    };
+    dest.truncate("multi_table_1").await?;
+    let copied = copy_table(
+        source.client()?,
+        source.get_created_table_name("users").unwrap(),
+        dest.client()?,
+        dest.get_created_table_name("multi_table_1").unwrap(),
+        &limited,
+    )
+    .await?;
+    assert_eq!(copied, 1);
+
+    Ok(())
+}
+
+#[cfg(feature = "test_utils")]
+#[tokio::test]
+async fn sweep_orphans_should_delete_stale_tables() -> Result<()> {
+    let mut config = TableConfig::load_from_file("fixtures/multi_table.yml")?;
+    config.tables.truncate(1);
+    config.tables[0].table_name = "sweep_test".to_string();
+    // Protected tables must be swept too
+    config.tables[0].deletion_protection = true;
+    config.delete_on_exit = false;
+
+    // Simulate a connector that never got to clean up
+    let orphaned = DynamodbConnector::try_new(config.clone()).await?;
+    let orphan_name = orphaned
+        .get_created_table_name("sweep_test")
+        .unwrap()
+        .to_string();
+    drop(orphaned);
+
+    config.sweep_orphans_older_than = Some(0);
+    let connector = DynamodbConnector::try_new(config).await?;
+    let new_name = connector.get_created_table_name("sweep_test").unwrap();
+
+    let tables = connector
+        .client()?
+        .list_tables()
+        .send()
+        .await
+        .map_err(|e| DynamoToolsError::Internal(format!("ListTables failed: {}", e)))?;
+    assert!(!tables.table_names().contains(&orphan_name));
+    assert!(tables.table_names().iter().any(|n| n == new_name));
+
+    let deleted = sweep_orphans(
+        connector.client()?,
+        &SweepTarget::Prefix("sweep_test-".to_string()),
+        std::time::Duration::from_secs(0),
+    )
+    .await?;
+    assert_eq!(deleted, vec![new_name.to_string()]);
+
+    Ok(())
+}
+
+#[cfg(feature = "test_utils")]
+#[tokio::test]
+async fn created_tables_should_be_tagged() -> Result<()> {
+    let mut config = TableConfig::load_from_file("fixtures/dev.yml")?;
+    config.tags = [("team".to_string(), "platform".to_string())].into();
+    config.tables[0].tags = [("team".to_string(), "identity".to_string())].into();
+    let connector = DynamodbConnector::try_new(config).await?;
+
+    let client = connector.client()?;
+    let table = client
+        .describe_table()
+        .table_name(connector.get_created_table_name("users").unwrap())
+        .send()
+        .await?
+        .table
+        .unwrap();
+    let resp = client
+        .list_tags_of_resource()
+        .resource_arn(table.table_arn().unwrap())
+        .send()
+        .await
+        .map_err(|e| DynamoToolsError::Internal(format!("ListTagsOfResource failed: {}", e)))?;
+    let tags: HashMap<&str, &str> = resp.tags().iter().map(|t| (t.key(), t.value())).collect();
+
+    assert_eq!(tags.get("created-by"), Some(&"dynamodb-tools"));
+    assert_eq!(tags.get("team"), Some(&"identity"));
+    assert!(tags.contains_key("run-id"));
+    assert!(tags.contains_key("hostname"));
+    assert!(tags.contains_key("created-at"));
+
+    Ok(())
+}
+
+#[cfg(feature = "test_utils")]
+#[tokio::test]
+async fn table_settings_should_be_applied() -> Result<()> {
+    let mut config = TableConfig::load_from_file("fixtures/multi_table.yml")?;
+    config.tables.truncate(1);
+    let table = &mut config.tables[0];
+    table.table_class = Some(TableClass::StandardInfrequentAccess);
+    table.deletion_protection = true;
+    table.point_in_time_recovery = true;
+    let connector = DynamodbConnector::try_new(config).await?;
+
+    let client = connector.client()?;
+    let table_name = connector.get_created_table_name("multi_table_1").unwrap();
+    let table = client
+        .describe_table()
+        .table_name(table_name)
+        .send()
+        .await?
+        .table
+        .unwrap();
+    assert_eq!(table.deletion_protection_enabled(), Some(true));
+
+    let backups = client
+        .describe_continuous_backups()
+        .table_name(table_name)
+        .send()
+        .await
+        .map_err(|e| {
+            DynamoToolsError::Internal(format!("DescribeContinuousBackups failed: {}", e))
+        })?;
+    let pitr_status = backups
+        .continuous_backups_description()
+        .and_then(|d| d.point_in_time_recovery_description())
+        .and_then(|d| d.point_in_time_recovery_status());
+    assert_eq!(
+        pitr_status,
+        Some(&aws_sdk_dynamodb::types::PointInTimeRecoveryStatus::Enabled)
+    );
+
+    Ok(())
+}
+
+#[cfg(feature = "test_utils")]
+#[tokio::test]
+async fn check_indexes_should_report_unprojected_attributes() -> Result<()> {
+    let mut config = TableConfig::load_from_file("fixtures/dev.yml")?;
+    config.tables[0].seed_data_file = None;
+    let connector = DynamodbConnector::try_new(config).await?;
+
+    connector
+        .seed_items(
+            "users",
+            vec![
+                serde_json::json!({
+                    "pk": "user_1", "sk": "profile", "name": "Alice",
+                    "gsi1pk": "email#alice", "gsi1sk": "profile", "lsi1sk": "2024-01-01"
+                }),
+                serde_json::json!({ "pk": "user_2", "sk": "profile", "name": "Bob" }),
+            ],
+        )
+        .await?;
+
+    let reports = connector.check_indexes("users").await?;
+    assert_eq!(reports.len(), 2);
+
+    // gsi1 only projects `pk` besides the keys
+    let gsi = &reports[0];
+    assert_eq!(gsi.index_name, "gsi1");
+    assert_eq!(gsi.items_checked, 1);
+    assert!(gsi.unqueryable.is_empty());
+    assert_eq!(gsi.missing_attributes.get("name"), Some(&1));
+    assert_eq!(gsi.missing_attributes.get("lsi1sk"), Some(&1));
+
+    // lsi1 projects all attributes
+    let lsi = &reports[1];
+    assert_eq!(lsi.index_name, "lsi1");
+    assert_eq!(lsi.items_checked, 1);
+    assert!(lsi.is_complete());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn workbench_model_should_create_seeded_tables() -> Result<()> {
+    let model = WorkbenchModel::load("fixtures/workbench_model.json")?;
+    let config = TableConfig::new(
+        "us-east-1".to_string(),
+        Some("http://localhost:8000".to_string()),
+        true,
+        vec![],
+    );
+    let connector = DynamodbConnector::try_from_workbench(config, &model).await?;
+
+    let table_name = connector
+        .get_created_table_name("shop")
+        .expect("Table 'shop' should have been created");
+    assert!(table_name.starts_with("shop-"));
+    // Table data plus the data of the Order facet
+    assert_eq!(count_items(&connector, "shop").await?, 3);
+
+    let resp = connector
+        .client()?
+        .describe_table()
+        .table_name(table_name)
+        .send()
+        .await
+        .unwrap();
+    let gsis = resp.table().unwrap().global_secondary_indexes();
+    assert_eq!(gsis[0].index_name(), Some("gsi1"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn table_handle_should_put_get_query_and_delete() -> Result<()> {
+    #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+    struct User {
+        pk: String,
+        sk: String,
+        name: String,
+    }
+
+    let mut config = TableConfig::load_from_file("fixtures/dev.yml")?;
+    config.tables[0].seed_data_file = None;
+    let connector = DynamodbConnector::try_new(config).await?;
+    let users = connector.table("users")?;
+    assert_eq!(
+        Some(users.name()),
+        connector.get_created_table_name("users")
+    );
+
+    let alice = User {
+        pk: "user_1".to_string(),
+        sk: "profile".to_string(),
+        name: "Alice".to_string(),
+    };
+    users.put(&alice).await?;
+    users
+        .put(&serde_json::json!({ "pk": "user_1", "sk": "settings", "name": "dark" }))
+        .await?;
+
+    let key = serde_json::json!({ "pk": "user_1", "sk": "profile" });
+    assert_eq!(users.get::<_, User>(&key).await?, Some(alice));
+    assert_eq!(users.query::<_, User>(&"user_1").await?.len(), 2);
+    assert_eq!(users.scan::<serde_json::Value>().await?.len(), 2);
+
+    users.delete(&key).await?;
+    assert_eq!(users.get::<_, User>(&key).await?, None);
+
+    let err = connector.table("unknown").unwrap_err();
+    assert!(matches!(err, DynamoToolsError::UnknownTable(name) if name == "unknown"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn assertion_helpers_should_check_seeded_data() -> Result<()> {
+    use serde_json::json;
+
+    let mut config = TableConfig::load_from_file("fixtures/dev.yml")?;
+    config.tables[0].seed_data_file = None;
+    let connector = DynamodbConnector::try_new(config).await?;
+    connector.seed("users", "fixtures/seed_users.json").await?;
+    connector
+        .table("users")?
+        .put(&json!({ "pk": "user_3", "sk": "profile", "gsi1pk": "email#carol", "gsi1sk": "user_3" }))
+        .await?;
+
+    let key = json!({ "pk": "user_1", "sk": "profile" });
+    connector.assert_item_exists("users", &key).await;
+    connector
+        .assert_item_eq(
+            "users",
+            &key,
+            json!({ "pk": "user_1", "sk": "profile", "name": "Alice", "email": "alice@example.com" }),
+        )
+        .await;
+    connector.assert_item_count("users", 4).await;
+    connector
+        .assert_index_contains(
+            "users",
+            "gsi1",
+            &json!({ "gsi1pk": "email#carol", "pk": "user_3" }),
+        )
+        .await;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn seeded_table_should_match_snapshot() -> Result<()> {
+    use dynamodb_tools::Redactions;
+
+    let connector = DynamodbConnector::load("fixtures/dev.yml").await?;
+    connector
+        .assert_table_snapshot("users", "fixtures/snapshots/users_seeded.json")
+        .await;
+
+    // Redacted volatile values keep the snapshot stable
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_millis();
+    connector
+        .table("users")?
+        .put(&serde_json::json!({
+            "pk": "user_3",
+            "sk": "session",
+            "updated_at": now.to_string(),
+            "token": "67e55044-10b1-426f-9247-bb680e5fe0c8",
+            "expires": "2030-01-01T00:00:00Z",
+        }))
+        .await?;
+    connector
+        .assert_table_snapshot_with(
+            "users",
+            "fixtures/snapshots/users_redacted.json",
+            &Redactions::new()
+                .attribute("updated_at")
+                .uuids()
+                .timestamps(),
+        )
+        .await;
+
+    Ok(())
+}
+
+#[tokio::test]
+#[should_panic(expected = "differs")]
+async fn assert_item_eq_should_panic_on_mismatch() {
+    use serde_json::json;
+
+    let connector = DynamodbConnector::load("fixtures/dev.yml").await.unwrap();
+    connector
+        .assert_item_eq(
+            "users",
+            &json!({ "pk": "user_1", "sk": "profile" }),
+            json!({ "pk": "user_1", "sk": "profile", "name": "Bob" }),
+        )
+        .await;
+}
+
+#[tokio::test]
+async fn entities_should_share_a_partition() -> Result<()> {
+    use dynamodb_tools::{Entity, EntityKeys};
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    struct User {
+        id: String,
+        name: String,
+    }
+
+    impl Entity for User {
+        const NAME: &'static str = "User";
+
+        fn keys() -> EntityKeys {
+            EntityKeys::new("USER#{id}").sk("PROFILE")
+        }
+    }
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    struct Order {
+        id: String,
+        order_id: String,
+        status: String,
+    }
+
+    impl Entity for Order {
+        const NAME: &'static str = "Order";
+
+        fn keys() -> EntityKeys {
+            EntityKeys::new("USER#{id}").sk("ORDER#{order_id}").gsi(
+                "gsi1",
+                "STATUS#{status}",
+                Some("ORDER#{order_id}"),
+            )
+        }
+    }
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    #[serde(tag = "entity_type")]
+    enum UserItem {
+        User(User),
+        Order(Order),
+    }
+
+    let mut config = TableConfig::load_from_file("fixtures/dev.yml")?;
+    config.tables[0].seed_data_file = None;
+    let connector = DynamodbConnector::try_new(config).await?;
+    let table = connector.table("users")?;
+    let users = table.entities::<User>()?;
+    let orders = table.entities::<Order>()?;
+
+    let alice = User {
+        id: "1".to_string(),
+        name: "Alice".to_string(),
+    };
+    let order = Order {
+        id: "1".to_string(),
+        order_id: "42".to_string(),
+        status: "open".to_string(),
+    };
+    users.put(&alice).await?;
+    orders.put(&order).await?;
+
+    let key = serde_json::json!({ "id": "1", "order_id": "42" });
+    assert_eq!(orders.get(&key).await?, Some(order.clone()));
+    assert_eq!(orders.list(&key).await?, vec![order.clone()]);
+    let open = serde_json::json!({ "status": "open" });
+    assert_eq!(
+        orders.query_index("gsi1", &open).await?,
+        vec![order.clone()]
+    );
+
+    let items: Vec<UserItem> = users.partition(&key).await?;
+    assert_eq!(items, vec![UserItem::Order(order), UserItem::User(alice)]);
+
+    orders.delete(&key).await?;
+    assert_eq!(orders.get(&key).await?, None);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn query_builder_should_page_through_index() -> Result<()> {
+    use futures::TryStreamExt;
+
+    let mut config = TableConfig::load_from_file("fixtures/dev.yml")?;
+    config.tables[0].seed_data_file = None;
+    let connector = DynamodbConnector::try_new(config).await?;
+    let users = connector.table("users")?;
+    for i in 0..5 {
+        users
+            .put(&serde_json::json!({
+                "pk": format!("user_{}", i), "sk": "order", "gsi1pk": "status#open",
+                "gsi1sk": format!("2024-01-0{}", i + 1), "total": i
+            }))
+            .await?;
+    }
+
+    let totals: Vec<i64> = users
+        .query_builder()
+        .index("gsi1")
+        .pk_eq("status#open")
+        .sk_ge("2024-01-02")
+        .filter("#total < :max")
+        .name("#total", "total")
+        .value(":max", 4)
+        .descending()
+        .page_size(2)
+        .stream::<serde_json::Value>()
+        .map_ok(|item| item["total"].as_i64().unwrap())
+        .try_collect()
+        .await?;
+    assert_eq!(totals, vec![3, 2, 1]);
+
+    let err = users
+        .query_builder()
+        .index("gsi1")
+        .pk_eq("status#open")
+        .consistent_read()
+        .collect::<serde_json::Value>()
+        .await
+        .unwrap_err();
+    assert!(matches!(err, DynamoToolsError::InvalidQuery(..)));
+
+    Ok(())
+}
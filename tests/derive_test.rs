@@ -0,0 +1,110 @@
+// tests/derive_test.rs
+#![cfg(feature = "derive")]
+use dynamodb_tools::{AttrType, DynamoTable, IndexProjection, TableConfig};
+use serde::{Deserialize, Serialize};
+
+#[allow(dead_code)]
+#[derive(Serialize, Deserialize, DynamoTable)]
+#[dynamo(table = "orders")]
+struct Order {
+    #[dynamo(pk)]
+    user_id: String,
+    #[dynamo(sk)]
+    #[dynamo(gsi = "by_status", sk)]
+    created_at: u64,
+    #[dynamo(gsi = "by_status", pk)]
+    #[serde(rename = "orderStatus")]
+    status: String,
+    #[dynamo(lsi = "by_total")]
+    total: Option<f64>,
+    #[dynamo(gsi = "by_receipt", pk, attr_type = "B")]
+    receipt: Vec<u8>,
+    note: Option<String>,
+}
+
+#[allow(dead_code)]
+#[derive(DynamoTable)]
+struct UserProfile<'a> {
+    #[dynamo(pk)]
+    id: &'a str,
+}
+
+#[allow(dead_code)]
+#[derive(Serialize, Deserialize, DynamoTable)]
+#[serde(rename_all = "camelCase")]
+struct Session {
+    #[dynamo(pk)]
+    user_id: String,
+    #[dynamo(sk)]
+    #[serde(rename(serialize = "startedAtMs", deserialize = "started"))]
+    started_at: u64,
+    #[dynamo(gsi = "by_type", pk)]
+    r#type: String,
+}
+
+#[test]
+fn derived_table_info_should_match_annotations() {
+    let info = Order::table_info();
+    assert_eq!(info.table_name, "orders");
+    assert_eq!(info.key_names(), vec!["user_id", "created_at"]);
+    assert_eq!(info.sk.as_ref().unwrap().attr_type, AttrType::N);
+
+    let attrs: Vec<_> = info
+        .attrs
+        .iter()
+        .map(|a| (a.name.as_str(), a.attr_type.clone()))
+        .collect();
+    assert_eq!(
+        attrs,
+        vec![
+            ("orderStatus", AttrType::S),
+            ("receipt", AttrType::B),
+            ("total", AttrType::N)
+        ]
+    );
+
+    assert_eq!(info.gsis.len(), 2);
+    let by_status = &info.gsis[0];
+    assert_eq!(by_status.name, "by_status");
+    assert_eq!(by_status.pk.name, "orderStatus");
+    assert_eq!(by_status.sk.as_ref().unwrap().name, "created_at");
+    assert_eq!(by_status.projection, Some(IndexProjection::All));
+    assert_eq!(info.gsis[1].sk.as_ref().map(|sk| &sk.name), None);
+
+    assert_eq!(info.lsis[0].name, "by_total");
+    assert_eq!(info.lsis[0].pk.name, "user_id");
+
+    // The derived schema is a valid table definition
+    let config = TableConfig::new("us-east-1".to_string(), None, false, vec![info]);
+    assert!(
+        aws_sdk_dynamodb::operation::create_table::CreateTableInput::try_from(
+            config.tables[0].clone()
+        )
+        .is_ok()
+    );
+}
+
+#[test]
+fn table_name_should_default_to_snake_case_struct_name() {
+    let info = UserProfile::table_info();
+    assert_eq!(info.table_name, "user_profile");
+    assert_eq!(info.sk.map(|sk| sk.name), None);
+    assert!(info.gsis.is_empty());
+}
+
+#[test]
+fn key_names_should_follow_serde_renames() {
+    let info = Session::table_info();
+    assert_eq!(info.key_names(), vec!["userId", "startedAtMs"]);
+    assert_eq!(info.gsis[0].pk.name, "type");
+
+    let item = serde_json::to_value(Session {
+        user_id: "u1".to_string(),
+        started_at: 1,
+        r#type: "web".to_string(),
+    })
+    .unwrap();
+    for name in info.key_names().iter().chain([&info.gsis[0].pk.name]) {
+        assert!(item.get(name).is_some(), "{}", name);
+    }
+}